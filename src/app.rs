@@ -1,54 +1,715 @@
 use eframe::egui;
-use crate::ui::dial::Dial;
+use crate::ui::dial::{Dial, DialRing};
+use crate::ui::chart_editor::ChartEditor;
+use crate::ui::ephemeris_panel::EphemerisPanel;
+use crate::ui::pictures_panel::PicturesPanel;
+use crate::ui::transits_panel::TransitsPanel;
+use crate::astrology::aspects::{Aspect, AspectEngine, AspectMotion, AspectSource};
+use crate::astrology::chart::{Chart, ChartPoint};
+use crate::astrology::houses::HouseSystem;
 use crate::astrology::harmonics::Harmonic;
+use crate::astrology::hits::{self, ArmHit};
+use crate::astrology::DegreePosition;
+use crate::astrology::constants::DEFAULT_MIDPOINT_ORB;
+use crate::astrology::midpoints::{MidpointList, MidpointTree};
+use crate::astrology::progressions::{self, Progression, ProgressionKey};
+use crate::astrology::solar_arc::{self, ArcMethod, TROPICAL_YEAR};
+use crate::astrology::time;
+use crate::astrology::transits::TransitTarget;
+
+/// Dials offered as buttons in the harmonic selector
+const HARMONIC_PRESETS: [Harmonic; 5] = [
+    Harmonic::First,
+    Harmonic::Second,
+    Harmonic::Fourth,
+    Harmonic::Eighth,
+    Harmonic::Sixteenth,
+];
+
+/// Highest harmonic the numeric entry accepts
+const MAX_HARMONIC: u32 = 10_000;
+
+/// Most charts shown at once as concentric rings
+const MAX_RINGS: usize = 3;
+
+/// Colour of each ring, outermost first
+const RING_COLORS: [egui::Color32; MAX_RINGS] = [
+    egui::Color32::from_rgb(30, 30, 140),
+    egui::Color32::from_rgb(170, 60, 30),
+    egui::Color32::from_rgb(20, 120, 70),
+];
+
+/// Oldest age in years the directions slider reaches
+const MAX_DIRECTION_AGE: f64 = 100.0;
+
+/// Id of the go-to field, which Ctrl+G focuses
+const GO_TO_ID: &str = "go_to";
+
+/// What a ring of the dial shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RingSource {
+    /// A loaded chart, by its index among the loaded charts
+    Chart(usize),
+    /// The working chart directed by solar arc to the directions date
+    SolarArc,
+    /// The working chart progressed to the directions date
+    Progressed,
+}
 
 pub struct DialApp {
     dial: Dial,
+    /// Every chart calculated or loaded this session
+    charts: Vec<Chart>,
+    /// What each ring shows; the first is always the chart being worked on
+    rings: [Option<RingSource>; MAX_RINGS],
+    chart_editor: ChartEditor,
+    house_system: HouseSystem,
+    show_houses: bool,
+    house_error: Option<String>,
+    midpoints: Option<MidpointList>,
+    tree_orb: f64,
+    /// Aspects of the current chart, the harmonic whose arms they include and
+    /// whether antiscia were included
+    aspects: Option<(Option<Harmonic>, bool, Vec<Aspect>)>,
+    show_aspects: bool,
+    harmonic_aspects: bool,
+    /// Include antiscia in the aspects, the midpoint tree and the arm hits
+    antiscia: bool,
+    /// What the arms point at, and the position, harmonic, orb and antiscia setting
+    /// it was found for
+    hits: Option<(DegreePosition, Harmonic, f64, bool, Vec<ArmHit>)>,
+    hit_orb: f64,
+    /// The working chart directed and progressed to the age on the directions slider
+    directed: Option<Chart>,
+    progressed: Option<Chart>,
+    direction_method: ArcMethod,
+    progression: Progression,
+    direction_age: f64,
+    pictures_panel: PicturesPanel,
+    transits_panel: TransitsPanel,
+    ephemeris_panel: EphemerisPanel,
+    show_ephemeris: bool,
+    /// Text typed into the go-to field, why it could not be read, and
+    /// whether the dial turns to it or jumps
+    go_to: String,
+    go_to_error: Option<String>,
+    animate_go_to: bool,
 }
 
 impl Default for DialApp {
     fn default() -> Self {
         Self {
-            dial: Dial::new(),
+            dial: Dial::default(),
+            charts: Vec::new(),
+            rings: [None; MAX_RINGS],
+            chart_editor: ChartEditor::default(),
+            house_system: HouseSystem::default(),
+            show_houses: false,
+            house_error: None,
+            midpoints: None,
+            tree_orb: DEFAULT_MIDPOINT_ORB as f64,
+            aspects: None,
+            show_aspects: true,
+            harmonic_aspects: false,
+            antiscia: false,
+            hits: None,
+            hit_orb: DEFAULT_MIDPOINT_ORB as f64,
+            directed: None,
+            progressed: None,
+            direction_method: ArcMethod::default(),
+            progression: Progression::default(),
+            direction_age: 0.0,
+            pictures_panel: PicturesPanel::default(),
+            transits_panel: TransitsPanel::default(),
+            ephemeris_panel: EphemerisPanel::default(),
+            show_ephemeris: false,
+            go_to: String::new(),
+            go_to_error: None,
+            animate_go_to: true,
         }
     }
 }
 
+impl DialApp {
+    /// The chart on the first ring, which midpoints, pictures and houses refer to
+    pub fn chart(&self) -> Option<&Chart> {
+        self.ring_chart(0)
+    }
+    
+    pub fn charts(&self) -> &[Chart] {
+        &self.charts
+    }
+    
+    pub fn ring_chart(&self, ring: usize) -> Option<&Chart> {
+        match self.rings.get(ring).copied().flatten()? {
+            RingSource::Chart(index) => self.charts.get(index),
+            RingSource::SolarArc => self.directed.as_ref(),
+            RingSource::Progressed => self.progressed.as_ref(),
+        }
+    }
+    
+    /// Adds a chart to the loaded ones, replacing an identical one, and shows it on the first ring
+    pub fn set_chart(&mut self, chart: Chart) {
+        let existing = self
+            .charts
+            .iter()
+            .position(|loaded| loaded.name == chart.name && loaded.birth == chart.birth);
+        let index = match existing {
+            Some(index) => {
+                self.charts[index] = chart;
+                index
+            }
+            None => {
+                self.charts.push(chart);
+                self.charts.len() - 1
+            }
+        };
+        self.set_ring(0, Some(index));
+    }
+    
+    /// Shows a loaded chart on a ring, or clears the ring with `None`
+    pub fn set_ring(&mut self, ring: usize, chart: Option<usize>) {
+        self.set_ring_source(ring, chart.map(RingSource::Chart));
+    }
+    
+    /// Shows a loaded or derived chart on a ring, or clears the ring with `None`.
+    /// The first ring only takes loaded charts, as the others derive from it.
+    pub fn set_ring_source(&mut self, ring: usize, source: Option<RingSource>) {
+        if ring >= MAX_RINGS || (ring == 0 && !matches!(source, Some(RingSource::Chart(_)))) {
+            return;
+        }
+        self.rings[ring] = source.filter(|source| match source {
+            RingSource::Chart(index) => *index < self.charts.len(),
+            RingSource::SolarArc | RingSource::Progressed => true,
+        });
+        if ring == 0 {
+            self.refresh_directions();
+        }
+        self.refresh_rings();
+        if ring == 0 {
+            self.midpoints = None;
+            self.aspects = None;
+            self.pictures_panel.clear();
+            self.transits_panel.clear();
+            self.ephemeris_panel.clear();
+            self.refresh_houses();
+            self.refresh_midpoints();
+            self.refresh_aspects();
+        }
+    }
+    
+    /// Hands the charts selected for the rings to the dial
+    fn refresh_rings(&mut self) {
+        let rings = (0..MAX_RINGS)
+            .filter_map(|ring| {
+                let chart = self.ring_chart(ring)?;
+                let points = chart
+                    .points()
+                    .iter()
+                    .map(|point| (point.id, point.position()))
+                    .collect();
+                Some(DialRing::new(chart.name.clone(), points, RING_COLORS[ring]))
+            })
+            .collect();
+        self.dial.set_rings(rings);
+        self.hits = None;
+    }
+    
+    /// Date the directions slider stands at, as a Julian Day in Universal Time
+    pub fn direction_jd(&self) -> Option<f64> {
+        Some(self.chart()?.birth.julian_day() + self.direction_age * TROPICAL_YEAR)
+    }
+    
+    /// Directs and progresses the working chart to the directions date again
+    fn refresh_directions(&mut self) {
+        (self.directed, self.progressed) = match (self.chart(), self.direction_jd()) {
+            (Some(chart), Some(jd)) => (
+                Some(solar_arc::directed_chart(chart, jd, self.direction_method)),
+                Some(progressions::progressed_chart(chart, jd, self.progression)),
+            ),
+            _ => (None, None),
+        };
+    }
+    
+    /// The charts shown on the dial, in the order of its rings
+    fn shown_charts(&self) -> Vec<&Chart> {
+        (0..MAX_RINGS).filter_map(|ring| self.ring_chart(ring)).collect()
+    }
+    
+    /// Returns the dial ring and midpoint tree of the point the red arm points at.
+    /// A point of an outer ring is read against the first ring's midpoints.
+    pub fn midpoint_tree(&self) -> Option<(usize, MidpointTree)> {
+        let chart = self.chart()?;
+        let (ring, focus) = self.dial.pointed_at()?;
+        let harmonic = self.dial.harmonic();
+        let tree = if ring > 0 {
+            let longitude = self.shown_charts().get(ring)?.point(focus)?.longitude;
+            MidpointTree::of_outer_point(chart.points(), focus, longitude, harmonic, self.tree_orb, self.antiscia)
+        } else if self.antiscia {
+            MidpointTree::with_antiscia(chart.points(), focus, harmonic, self.tree_orb)?
+        } else {
+            MidpointTree::new(chart.points(), focus, harmonic, self.tree_orb)?
+        };
+        Some((ring, tree))
+    }
+    
+    /// Rebuilds the midpoint list when the chart or the dial's harmonic changed
+    fn refresh_midpoints(&mut self) {
+        let harmonic = self.dial.harmonic();
+        let stale = match &self.midpoints {
+            Some(list) => list.harmonic != harmonic,
+            None => true,
+        };
+        if stale || self.chart().is_none() {
+            self.midpoints = self
+                .chart()
+                .map(|chart| MidpointList::new(chart.points(), harmonic));
+        }
+    }
+    
+    /// Aspects of the current chart, closest first
+    pub fn aspects(&self) -> &[Aspect] {
+        self.aspects.as_ref().map_or(&[], |(_, _, aspects)| aspects.as_slice())
+    }
+    
+    /// Recalculates aspects when the chart, the dial's harmonic or the aspect
+    /// settings changed, and hands their lines to the dial
+    fn refresh_aspects(&mut self) {
+        let harmonic = self.harmonic_aspects.then(|| self.dial.harmonic());
+        let stale = match &self.aspects {
+            Some((used, antiscia, _)) => *used != harmonic || *antiscia != self.antiscia,
+            None => true,
+        };
+        if !stale {
+            return;
+        }
+        
+        let mut engine = AspectEngine::new();
+        if let Some(harmonic) = harmonic {
+            engine = engine.with_harmonic(harmonic);
+        }
+        engine.antiscia = self.antiscia;
+        let aspects = self.chart().map_or_else(Vec::new, |chart| engine.find(chart.points()));
+        self.aspects = Some((harmonic, self.antiscia, aspects));
+        self.refresh_aspect_lines();
+    }
+    
+    /// Draws the current aspects on the dial, or clears them when hidden
+    fn refresh_aspect_lines(&mut self) {
+        let lines = match (self.chart(), self.show_aspects) {
+            (Some(chart), true) => self
+                .aspects()
+                .iter()
+                .filter_map(|aspect| {
+                    // A mirrored aspect is drawn from the mirror image of the first point
+                    let first = chart.point(aspect.first)?.longitude;
+                    let first = aspect.mirror.map_or(first, |mirror| mirror.reflect(first));
+                    let first = DegreePosition::from_degrees(first);
+                    let second = chart.position(aspect.second)?;
+                    Some((first, second, aspect_color(aspect)))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        self.dial.set_aspect_lines(lines);
+    }
+    
+    /// Points and midpoints of every ring under any arm, nearest first
+    pub fn hits(&self) -> &[ArmHit] {
+        self.hits.as_ref().map_or(&[], |(_, _, _, _, hits)| hits.as_slice())
+    }
+    
+    /// Looks again at what the arms point at when the dial moved or its harmonic changed
+    fn refresh_hits(&mut self) {
+        let position = self.dial.position();
+        let harmonic = self.dial.harmonic();
+        let stale = match &self.hits {
+            Some((at, on, orb, antiscia, _)) => {
+                *at != position || *on != harmonic || *orb != self.hit_orb || *antiscia != self.antiscia
+            }
+            None => true,
+        };
+        if !stale {
+            return;
+        }
+        let rings: Vec<&[ChartPoint]> = self.shown_charts().into_iter().map(Chart::points).collect();
+        let found = hits::arm_hits(&rings, position.to_degrees(), harmonic, self.hit_orb, self.antiscia);
+        self.hits = Some((position, harmonic, self.hit_orb, self.antiscia, found));
+    }
+    
+    /// Reads a position typed in: degrees, sign or modality notation, or a point,
+    /// midpoint or sensitive point of the chart such as "SU/MO"
+    pub fn resolve_position(&self, text: &str) -> Result<DegreePosition, String> {
+        let position_error = match text.parse::<DegreePosition>() {
+            Ok(position) => return Ok(position),
+            Err(err) => err,
+        };
+        let target = match text.trim().parse::<TransitTarget>() {
+            Ok(target) => target,
+            // Numbers mean a position was meant rather than a point
+            Err(_) if text.trim().is_empty() || text.chars().any(|c| c.is_ascii_digit()) => {
+                return Err(position_error.to_string());
+            }
+            Err(err) => return Err(err.to_string()),
+        };
+        let chart = self.chart().ok_or("No chart to find the points in")?;
+        let longitudes = target.longitudes(chart, Harmonic::First).map_err(|err| err.to_string())?;
+        Ok(DegreePosition::from_degrees(longitudes[0]))
+    }
+    
+    /// Turns the dial to the position in the go-to field, or keeps the reason it cannot
+    fn go_to(&mut self) {
+        match self.resolve_position(&self.go_to) {
+            Ok(position) => {
+                self.go_to_error = None;
+                self.dial.go_to(position, self.animate_go_to);
+            }
+            Err(err) => self.go_to_error = Some(err),
+        }
+    }
+    
+    /// Recalculates the house ring for the current chart and house system
+    fn refresh_houses(&mut self) {
+        self.house_error = None;
+        let houses = match (self.chart(), self.show_houses) {
+            (Some(chart), true) => match chart.houses(self.house_system) {
+                Ok(houses) => Some(houses),
+                Err(err) => {
+                    self.house_error = Some(err.to_string());
+                    None
+                }
+            },
+            _ => None,
+        };
+        self.dial.set_houses(houses);
+    }
+}
+
+/// Hard aspects in red, trines and sextiles in blue, the rest in green
+fn aspect_color(aspect: &Aspect) -> egui::Color32 {
+    if aspect.aspect.is_hard() {
+        egui::Color32::from_rgb(200, 40, 40)
+    } else if aspect.aspect.source == AspectSource::Major {
+        egui::Color32::from_rgb(40, 80, 200)
+    } else {
+        egui::Color32::from_rgb(40, 150, 80)
+    }
+}
+
 impl eframe::App for DialApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Handle keyboard input
+        // Ctrl+G puts the cursor in the go-to field
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::G)) {
+            ctx.memory_mut(|memory| memory.request_focus(egui::Id::new(GO_TO_ID)));
+        }
+        
+        // Handle keyboard input, unless it is meant for a text field
+        let typing = ctx.wants_keyboard_input();
         ctx.input(|i| {
             self.dial.update_modifiers(i.modifiers);
+            if typing {
+                return;
+            }
+            
+            for key in [
+                egui::Key::ArrowLeft,
+                egui::Key::ArrowRight,
+                egui::Key::PageUp,
+                egui::Key::PageDown,
+                egui::Key::Home,
+            ] {
+                if i.key_pressed(key) {
+                    self.dial.handle_key(key);
+                }
+            }
+        });
+
+        egui::SidePanel::left("chart_panel").show(ctx, |ui| {
+            ui.heading("Chart");
+            let current = match self.rings[0] {
+                Some(RingSource::Chart(index)) => self.charts.get(index),
+                _ => None,
+            };
+            if let Some(chart) = self.chart_editor.ui(ui, current) {
+                self.set_chart(chart);
+            }
+            
+            ui.separator();
+            ui.label("Rings, outermost first:");
+            for (ring, color) in RING_COLORS.into_iter().enumerate() {
+                let selected = self.rings[ring];
+                let mut choice = selected;
+                let name = |source: Option<RingSource>| match source {
+                    Some(RingSource::Chart(index)) => match self.charts.get(index) {
+                        Some(chart) if !chart.name.is_empty() => chart.name.clone(),
+                        _ => "Unnamed".to_string(),
+                    },
+                    Some(RingSource::SolarArc) => self.direction_method.name().to_string(),
+                    Some(RingSource::Progressed) => self.progression.name(),
+                    None => "None".to_string(),
+                };
+                egui::ComboBox::from_id_source(("ring", ring))
+                    .selected_text(egui::RichText::new(name(selected)).color(color))
+                    .show_ui(ui, |ui| {
+                        // The first ring always holds the working chart
+                        if ring > 0 {
+                            ui.selectable_value(&mut choice, None, "None");
+                        }
+                        for index in 0..self.charts.len() {
+                            let source = Some(RingSource::Chart(index));
+                            ui.selectable_value(&mut choice, source, name(source));
+                        }
+                        if ring > 0 {
+                            for source in [RingSource::SolarArc, RingSource::Progressed] {
+                                ui.selectable_value(&mut choice, Some(source), name(Some(source)));
+                            }
+                        }
+                    });
+                if choice != selected {
+                    self.set_ring_source(ring, choice);
+                }
+            }
             
-            if i.key_pressed(egui::Key::ArrowLeft) {
-                self.dial.handle_key(egui::Key::ArrowLeft);
+            ui.separator();
+            let mut changed = ui.checkbox(&mut self.show_houses, "Show houses").changed();
+            egui::ComboBox::from_label("Houses")
+                .selected_text(self.house_system.name())
+                .show_ui(ui, |ui| {
+                    for system in HouseSystem::ALL {
+                        changed |= ui
+                            .selectable_value(&mut self.house_system, system, system.name())
+                            .changed();
+                    }
+                });
+            if changed {
+                self.refresh_houses();
+            }
+            if let Some(err) = &self.house_error {
+                ui.colored_label(egui::Color32::RED, err);
             }
-            if i.key_pressed(egui::Key::ArrowRight) {
-                self.dial.handle_key(egui::Key::ArrowRight);
+            
+            ui.separator();
+            egui::CollapsingHeader::new("Directions and progressions").show(ui, |ui| {
+                let mut changed = false;
+                egui::ComboBox::from_id_source("direction_method")
+                    .selected_text(self.direction_method.name())
+                    .show_ui(ui, |ui| {
+                        for method in ArcMethod::ALL {
+                            changed |= ui
+                                .selectable_value(&mut self.direction_method, method, method.name())
+                                .changed();
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_source("progression_key")
+                        .selected_text(self.progression.key.name())
+                        .show_ui(ui, |ui| {
+                            for key in ProgressionKey::ALL {
+                                changed |= ui
+                                    .selectable_value(&mut self.progression.key, key, key.name())
+                                    .changed();
+                            }
+                        });
+                    changed |= ui.checkbox(&mut self.progression.converse, "Converse").changed();
+                });
+                changed |= ui
+                    .add(
+                        egui::Slider::new(&mut self.direction_age, 0.0..=MAX_DIRECTION_AGE)
+                            .text("Age")
+                            .suffix(" y"),
+                    )
+                    .changed();
+                if ui.button("Today").clicked() {
+                    if let Some(chart) = self.chart() {
+                        let now = time::julian_day(&chrono::Utc::now());
+                        self.direction_age = solar_arc::age(&chart.birth, now).clamp(0.0, MAX_DIRECTION_AGE);
+                        changed = true;
+                    }
+                }
+                if changed {
+                    self.refresh_directions();
+                    self.refresh_rings();
+                }
+                
+                match (self.chart(), &self.directed, self.direction_jd()) {
+                    (Some(chart), Some(directed), Some(jd)) => {
+                        ui.label(format!("Date: {}", directed.birth.datetime.format("%Y-%m-%d")));
+                        let arc = solar_arc::solar_arc(chart, jd, self.direction_method);
+                        ui.label(format!("Arc: {}", DegreePosition::from_degrees(arc.rem_euclid(360.0)).format()));
+                        ui.horizontal(|ui| {
+                            for (source, label) in [
+                                (RingSource::SolarArc, "Show directed ring"),
+                                (RingSource::Progressed, "Show progressed ring"),
+                            ] {
+                                if !self.rings.contains(&Some(source)) && ui.button(label).clicked() {
+                                    self.set_ring_source(1, Some(source));
+                                }
+                            }
+                        });
+                    }
+                    _ => {
+                        ui.label("No chart");
+                    }
+                }
+            });
+            
+            ui.separator();
+            egui::CollapsingHeader::new("Aspects").show(ui, |ui| {
+                if ui.checkbox(&mut self.show_aspects, "Draw on dial").changed() {
+                    self.refresh_aspect_lines();
+                }
+                ui.checkbox(&mut self.harmonic_aspects, "Include harmonic arms");
+                ui.checkbox(&mut self.antiscia, "Include antiscia");
+                egui::ScrollArea::vertical().id_source("aspect_list").max_height(200.0).show(ui, |ui| {
+                    egui::Grid::new("aspect_list").striped(true).show(ui, |ui| {
+                        for aspect in self.aspects() {
+                            ui.colored_label(aspect_color(aspect), aspect.label());
+                            ui.monospace(DegreePosition::from_degrees(aspect.orb).format());
+                            ui.label(match aspect.motion {
+                                AspectMotion::Applying => "applying",
+                                AspectMotion::Separating => "separating",
+                                AspectMotion::Stationary => "",
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+            
+            ui.separator();
+            egui::CollapsingHeader::new("Planetary pictures").show(ui, |ui| {
+                let chart = match self.rings[0] {
+                    Some(RingSource::Chart(index)) => self.charts.get(index),
+                    _ => None,
+                };
+                self.pictures_panel.ui(ui, chart, self.dial.harmonic());
+            });
+            
+            egui::CollapsingHeader::new("Transits").show(ui, |ui| {
+                let chart = match self.rings[0] {
+                    Some(RingSource::Chart(index)) => self.charts.get(index),
+                    _ => None,
+                };
+                self.transits_panel.ui(ui, chart, self.dial.harmonic());
+            });
+            
+            ui.checkbox(&mut self.show_ephemeris, "Graphic ephemeris");
+        });
+
+        let chart = match self.rings[0] {
+            Some(RingSource::Chart(index)) => self.charts.get(index),
+            _ => None,
+        };
+        egui::Window::new("Graphic ephemeris")
+            .open(&mut self.show_ephemeris)
+            .default_size([700.0, 450.0])
+            .show(ctx, |ui| {
+                self.ephemeris_panel.ui(ui, chart, self.dial.harmonic());
+            });
+        
+        self.refresh_midpoints();
+        self.refresh_aspects();
+        if let Some((ring, tree)) = self.midpoint_tree() {
+            let ring = &self.dial.rings()[ring];
+            let heading = format!("{} tree", tree.focus.name());
+            let heading = egui::RichText::new(heading).color(ring.color);
+            let label = ring.label.clone();
+            egui::SidePanel::right("tree_panel").show(ctx, |ui| {
+                ui.heading(heading);
+                if !label.is_empty() {
+                    ui.label(label);
+                }
+                ui.add(
+                    egui::Slider::new(&mut self.tree_orb, 0.1..=3.0)
+                        .text("Orb")
+                        .suffix("°"),
+                );
+                if tree.is_empty() {
+                    ui.label("No midpoints within orb");
+                }
+                egui::Grid::new("midpoint_tree").striped(true).show(ui, |ui| {
+                    for entry in tree.entries() {
+                        let mirror = entry
+                            .mirror
+                            .map_or(String::new(), |mirror| format!("({}) ", mirror.abbreviation()));
+                        ui.label(format!("{}= {}", mirror, entry.midpoint.label()));
+                        ui.monospace(DegreePosition::from_degrees(entry.orb).format());
+                        ui.label(if entry.direct {
+                            "direct".to_string()
+                        } else {
+                            format!("indirect {}°", entry.aspect.min(360.0 - entry.aspect))
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+        
+        egui::SidePanel::right("midpoint_panel").show(ctx, |ui| {
+            ui.heading("Midpoints");
+            match &self.midpoints {
+                Some(list) => {
+                    ui.label(format!("{} on the {}° dial", list.len(), list.harmonic.angle_span()));
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("midpoint_list").striped(true).show(ui, |ui| {
+                            for entry in list.entries() {
+                                ui.monospace(entry.degree_position().format());
+                                ui.label(entry.midpoint.label());
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+                None => {
+                    ui.label("No chart");
+                }
             }
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Uranian Astrology Dial");
+            ui.heading(match self.chart() {
+                Some(chart) if !chart.name.is_empty() => chart.name.as_str(),
+                _ => "Uranian Astrology Dial",
+            });
             
-            // Harmonic selector
+            // Harmonic selector: the common dials, or any harmonic by number
             ui.horizontal(|ui| {
                 ui.label("Harmonic:");
                 
-                if ui.selectable_value(&mut self.dial.harmonic(), Harmonic::First, "1st (360°)").clicked() {
-                    self.dial.set_harmonic(Harmonic::First);
+                for harmonic in HARMONIC_PRESETS {
+                    if ui.selectable_label(self.dial.harmonic() == harmonic, harmonic.display_name()).clicked() {
+                        self.dial.switch_harmonic(harmonic);
+                    }
+                }
+                
+                let mut number = self.dial.harmonic().to_number();
+                let entry = ui.add(egui::DragValue::new(&mut number).clamp_range(1..=MAX_HARMONIC).prefix("H"));
+                if entry.changed() {
+                    if let Some(harmonic) = Harmonic::from_number(number) {
+                        self.dial.switch_harmonic(harmonic);
+                    }
                 }
-                if ui.selectable_value(&mut self.dial.harmonic(), Harmonic::Second, "2nd (180°)").clicked() {
-                    self.dial.set_harmonic(Harmonic::Second);
+                ui.label(self.dial.harmonic().display_name());
+                
+                let mut harmonic_face = self.dial.harmonic_face();
+                if ui.checkbox(&mut harmonic_face, "Harmonic face").changed() {
+                    self.dial.set_harmonic_face(harmonic_face);
                 }
-                if ui.selectable_value(&mut self.dial.harmonic(), Harmonic::Fourth, "4th (90°)").clicked() {
-                    self.dial.set_harmonic(Harmonic::Fourth);
+                
+                let mut mirror_axis = self.dial.mirror_axis();
+                if ui.checkbox(&mut mirror_axis, "Mirror axis").changed() {
+                    self.dial.set_mirror_axis(mirror_axis);
                 }
-                if ui.selectable_value(&mut self.dial.harmonic(), Harmonic::Eighth, "8th (45°)").clicked() {
-                    self.dial.set_harmonic(Harmonic::Eighth);
+            });
+            ui.horizontal(|ui| {
+                let mut speed = self.dial.animation_speed();
+                let slider = egui::Slider::new(&mut speed, 0.0..=3.0).text("Animation speed (0 = off)");
+                if ui.add(slider).changed() {
+                    self.dial.set_animation_speed(speed);
                 }
-                if ui.selectable_value(&mut self.dial.harmonic(), Harmonic::Sixteenth, "16th (22.5°)").clicked() {
-                    self.dial.set_harmonic(Harmonic::Sixteenth);
+                let mut inertia = self.dial.inertia();
+                if ui.checkbox(&mut inertia, "Coast after a flick").changed() {
+                    self.dial.set_inertia(inertia);
                 }
             });
             
@@ -60,9 +721,301 @@ impl eframe::App for DialApp {
             ui.horizontal(|ui| {
                 ui.label("Position:");
                 ui.monospace(self.dial.position().format());
+                let span = self.dial.face_span();
+                if span < 360.0 {
+                    let reading = self.dial.position().to_degrees().rem_euclid(span);
+                    ui.label("Dial:");
+                    ui.monospace(DegreePosition::from_degrees(reading).format());
+                }
                 ui.separator();
-                ui.label("Controls: Shift = 1° | Ctrl = 1' | Shift+Ctrl = 1\"");
+                ui.label("Controls: Shift = 1° | Ctrl = 1' | Shift+Ctrl = 1\" | PgUp/PgDn = 30° | Home = 0° | Ctrl+G = go to");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Go to:");
+                let field = ui.add(
+                    egui::TextEdit::singleline(&mut self.go_to)
+                        .id(egui::Id::new(GO_TO_ID))
+                        .hint_text("3°45' Leo, 3\u{264C}45 or SU/MO")
+                        .desired_width(160.0),
+                );
+                if field.changed() {
+                    self.go_to_error = None;
+                }
+                let entered = field.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button("Go").clicked() || entered {
+                    self.go_to();
+                }
+                ui.checkbox(&mut self.animate_go_to, "Animate");
+                if let Some(err) = &self.go_to_error {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+            });
+            
+            // Points and midpoints under the arms, following the dial as it turns
+            self.refresh_hits();
+            ui.horizontal(|ui| {
+                ui.label("Under the arms:");
+                ui.add(
+                    egui::DragValue::new(&mut self.hit_orb)
+                        .clamp_range(0.0..=3.0)
+                        .speed(0.05)
+                        .prefix("orb ")
+                        .suffix("°"),
+                );
+                if self.chart().is_some() && self.hits().is_empty() {
+                    ui.label("nothing within orb");
+                }
+            });
+            egui::ScrollArea::vertical().id_source("arm_hits").max_height(120.0).show(ui, |ui| {
+                egui::Grid::new("arm_hits").striped(true).show(ui, |ui| {
+                    for hit in self.hits() {
+                        let color = self.dial.rings().get(hit.ring).map_or(egui::Color32::GRAY, |ring| ring.color);
+                        ui.colored_label(color, hit.label());
+                        ui.monospace(format!("arm {}", hit.arm_position().format()));
+                        ui.monospace(DegreePosition::from_degrees(hit.orb).format());
+                        ui.end_row();
+                    }
+                });
             });
         });
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::BirthData;
+    use crate::astrology::antiscia::Mirror;
+    use crate::astrology::hits::HitTarget;
+    use crate::astrology::{Body, PointId};
+    use chrono::{FixedOffset, TimeZone};
+    
+    #[test]
+    fn test_set_chart() {
+        let datetime = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 1, 1, 12, 0, 0)
+            .unwrap();
+        let chart = Chart::new("Sample", BirthData::new(datetime, 0.0, 0.0).unwrap());
+        
+        let mut app = DialApp::default();
+        assert!(app.chart().is_none());
+        app.set_chart(chart);
+        assert_eq!(app.dial.points().len(), app.chart().unwrap().points().len());
+        assert!(app.dial.houses().is_none());
+        assert_eq!(app.midpoints.as_ref().unwrap().harmonic, Harmonic::First);
+        
+        app.dial.set_harmonic(Harmonic::Fourth);
+        app.refresh_midpoints();
+        assert_eq!(app.midpoints.as_ref().unwrap().harmonic, Harmonic::Fourth);
+        
+        let sun = app.chart().unwrap().position(PointId::Body(Body::Sun)).unwrap();
+        app.dial.set_position(sun);
+        assert_eq!(app.midpoint_tree().unwrap().1.focus, PointId::Body(Body::Sun));
+        
+        assert!(!app.aspects().is_empty());
+        assert_eq!(app.dial.aspect_lines().len(), app.aspects().len());
+        app.harmonic_aspects = true;
+        let classical = app.aspects().len();
+        app.refresh_aspects();
+        assert!(app.aspects().len() >= classical);
+        app.antiscia = true;
+        app.refresh_aspects();
+        assert!(app.aspects().iter().any(|aspect| aspect.mirror.is_some()));
+        
+        app.refresh_hits();
+        assert!(app.hits().iter().any(|hit| hit.target == HitTarget::Point(PointId::Body(Body::Sun))));
+        // The Sun's antiscion lies under the arm at 180° when the red arm is on 0° Capricorn
+        app.dial.set_position(DegreePosition::from_degrees(Mirror::Antiscion.reflect(sun.to_degrees()) + 180.0));
+        app.refresh_hits();
+        let sun_hit = HitTarget::Point(PointId::Body(Body::Sun));
+        assert!(app.hits().iter().any(|hit| hit.target == sun_hit && hit.mirror == Some(Mirror::Antiscion)));
+        
+        app.show_houses = true;
+        app.refresh_houses();
+        assert_eq!(app.dial.houses().unwrap().system, HouseSystem::Meridian);
+    }
+    
+    #[test]
+    fn test_new_chart_rebuilds_midpoints() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let first = offset.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        let second = offset.with_ymd_and_hms(1980, 6, 15, 8, 30, 0).unwrap();
+        let first = Chart::new("First", BirthData::new(first, 0.0, 0.0).unwrap());
+        let second = Chart::new("Second", BirthData::new(second, 0.0, 0.0).unwrap());
+
+        // Same harmonic, different chart: the list must not be kept
+        let mut app = DialApp::default();
+        app.set_chart(first);
+        app.set_chart(second.clone());
+        app.refresh_midpoints();
+        let expected = MidpointList::new(second.points(), Harmonic::First);
+        assert_eq!(app.midpoints.as_ref(), Some(&expected));
+    }
+    
+    #[test]
+    fn test_rings() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let natal = Chart::new(
+            "Natal",
+            BirthData::new(offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap(), 53.55, 10.0).unwrap(),
+        );
+        let transits = Chart::new(
+            "Transits",
+            BirthData::new(offset.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), 53.55, 10.0).unwrap(),
+        );
+        
+        let mut app = DialApp::default();
+        app.set_chart(natal.clone());
+        app.set_chart(transits);
+        assert_eq!(app.charts().len(), 2);
+        assert_eq!(app.chart().unwrap().name, "Transits");
+        
+        // Recalculating a chart replaces it rather than adding another
+        app.set_chart(natal);
+        assert_eq!(app.charts().len(), 2);
+        assert_eq!(app.chart().unwrap().name, "Natal");
+        
+        app.set_ring(1, Some(1));
+        assert_eq!(app.ring_chart(1).unwrap().name, "Transits");
+        assert_eq!(app.dial.rings().len(), 2);
+        assert_eq!(app.dial.rings()[1].label, "Transits");
+        
+        app.set_ring(1, None);
+        app.set_ring(2, Some(5));
+        assert_eq!(app.dial.rings().len(), 1);
+    }
+    
+    #[test]
+    fn test_outer_ring_under_arms() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let natal = Chart::new(
+            "Natal",
+            BirthData::new(offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap(), 53.55, 10.0).unwrap(),
+        );
+        let transits = Chart::new(
+            "Transits",
+            BirthData::new(offset.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), 53.55, 10.0).unwrap(),
+        );
+        let saturn = transits.position(PointId::Body(Body::Saturn)).unwrap();
+        
+        let mut app = DialApp::default();
+        app.set_chart(transits);
+        app.set_chart(natal);
+        app.set_ring_source(1, Some(RingSource::SolarArc));
+        app.set_ring(2, Some(0));
+        assert_eq!(app.dial.rings().len(), 3);
+        
+        // Transit Saturn on the third ring is read by the red arm
+        app.dial.set_position(saturn);
+        app.refresh_hits();
+        let saturn_hit = HitTarget::Point(PointId::Body(Body::Saturn));
+        assert!(app.hits().iter().any(|hit| hit.ring == 2 && hit.target == saturn_hit && hit.orb < 1e-3));
+        
+        let (ring, tree) = app.midpoint_tree().unwrap();
+        assert_eq!(ring, 2);
+        assert_eq!(tree.focus, PointId::Body(Body::Saturn));
+        // Natal Saturn's own pairs count against a transiting Saturn
+        let natal_points = app.chart().unwrap().points();
+        let expected = MidpointTree::of_outer_point(
+            natal_points,
+            PointId::Body(Body::Saturn),
+            saturn.to_degrees(),
+            app.dial.harmonic(),
+            app.tree_orb,
+            false,
+        );
+        assert_eq!(tree.len(), expected.len());
+    }
+    
+    #[test]
+    fn test_solar_arc_ring() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let natal = Chart::new(
+            "Natal",
+            BirthData::new(offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap(), 53.55, 10.0).unwrap(),
+        );
+        
+        let mut app = DialApp::default();
+        app.set_chart(natal);
+        app.set_ring_source(0, Some(RingSource::SolarArc));
+        assert_eq!(app.chart().unwrap().name, "Natal");
+        
+        app.set_ring_source(1, Some(RingSource::SolarArc));
+        assert_eq!(app.ring_chart(1).unwrap().name, "Natal (Solar arc)");
+        let sun = PointId::Body(Body::Sun);
+        assert_eq!(app.ring_chart(1).unwrap().position(sun), app.chart().unwrap().position(sun));
+        
+        // Moving the slider carries the directed ring along
+        app.direction_age = 30.0;
+        app.refresh_directions();
+        app.refresh_rings();
+        let natal_sun = app.chart().unwrap().point(sun).unwrap().longitude;
+        let directed_sun = app.ring_chart(1).unwrap().point(sun).unwrap().longitude;
+        let arc = (directed_sun - natal_sun).rem_euclid(360.0);
+        assert!(arc > 28.5 && arc < 29.5);
+        assert_eq!(app.dial.rings()[1].points[0].1, app.ring_chart(1).unwrap().position(sun).unwrap());
+    }
+    
+    #[test]
+    fn test_progressed_ring() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let natal = Chart::new(
+            "Natal",
+            BirthData::new(offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap(), 53.55, 10.0).unwrap(),
+        );
+        
+        let mut app = DialApp::default();
+        app.set_chart(natal);
+        app.set_ring_source(0, Some(RingSource::Progressed));
+        assert_eq!(app.chart().unwrap().name, "Natal");
+        
+        app.progression = Progression::new(ProgressionKey::Tertiary, true);
+        app.direction_age = 1.0;
+        app.refresh_directions();
+        app.set_ring_source(2, Some(RingSource::Progressed));
+        assert_eq!(app.ring_chart(2).unwrap().name, "Natal (Converse tertiary)");
+        assert_eq!(app.dial.rings().len(), 2);
+        assert_eq!(app.dial.rings()[1].label, "Natal (Converse tertiary)");
+    }
+    
+    #[test]
+    fn test_go_to() {
+        let mut app = DialApp::default();
+        assert_eq!(app.resolve_position("3°45' Leo"), Ok(DegreePosition::new(123, 45, 0)));
+        assert_eq!(app.resolve_position("123.75"), Ok(DegreePosition::new(123, 45, 0)));
+        assert_eq!(app.resolve_position("SU/MO"), Err("No chart to find the points in".to_string()));
+        assert_eq!(app.resolve_position("3°75'"), Err("Minutes must be below 60, got 75".to_string()));
+        assert_eq!(app.resolve_position("XX"), Err("Unknown point \"XX\"".to_string()));
+        
+        let offset = FixedOffset::east_opt(0).unwrap();
+        app.set_chart(Chart::new(
+            "Natal",
+            BirthData::new(offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap(), 53.55, 10.0).unwrap(),
+        ));
+        let chart = app.chart().unwrap();
+        let (sun, moon) = (PointId::Body(Body::Sun), PointId::Body(Body::Moon));
+        assert_eq!(app.resolve_position("su"), Ok(chart.position(sun).unwrap()));
+        let midpoint = crate::astrology::midpoints::midpoint(
+            chart.point(sun).unwrap().longitude,
+            chart.point(moon).unwrap().longitude,
+        );
+        assert_eq!(app.resolve_position("SU/MO"), Ok(DegreePosition::from_degrees(midpoint)));
+        
+        // The field turns the dial, or leaves it and says why
+        app.animate_go_to = false;
+        app.go_to = "SU/MO".to_string();
+        app.go_to();
+        assert_eq!(app.dial.position(), DegreePosition::from_degrees(midpoint));
+        app.go_to = "40 Leo".to_string();
+        app.go_to();
+        assert_eq!(app.dial.position(), DegreePosition::from_degrees(midpoint));
+        assert_eq!(app.go_to_error.as_deref(), Some("Degrees must be below 30, got 40"));
+        
+        app.animate_go_to = true;
+        app.go_to = "0 fixed".to_string();
+        app.go_to();
+        assert!(app.go_to_error.is_none());
+        assert_eq!(app.dial.target_position(), DegreePosition::new(30, 0, 0));
+    }
+}
@@ -1,128 +1,122 @@
-use std::f32::consts::PI;
 use crate::utils::math::{normalize_degrees, degrees_to_radians, radians_to_degrees};
 
+/// Arcseconds in a full circle
+pub const ARCSECONDS_PER_CIRCLE: u32 = 360 * 3600;
+
 /// Represents an angular position in degrees, minutes, and seconds
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// The position is held as a whole number of arcseconds, so adding, subtracting
+/// and stepping are exact and the fields can never overflow, e.g. to 60 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DegreePosition {
-    degrees: u16,
-    minutes: u8,
-    seconds: u8,
+    /// Arcseconds from 0°, always less than a full circle
+    arcseconds: u32,
 }
 
 impl DegreePosition {
     /// Creates a new DegreePosition
-    pub fn new(degrees: u16, minutes: u8, seconds: u8) -> Self {
-        // Normalize values
-        let mut total_seconds = seconds;
-        let mut total_minutes = minutes;
-        
-        if total_seconds >= 60 {
-            total_minutes += total_seconds / 60;
-            total_seconds %= 60;
-        }
-        
-        let mut total_degrees = degrees;
-        if total_minutes >= 60 {
-            total_degrees += total_minutes as u16 / 60;
-            total_minutes %= 60;
-        }
-        
-        total_degrees %= 360;
-        
+    pub const fn new(degrees: u16, minutes: u8, seconds: u8) -> Self {
+        // Overflowing minutes and seconds carry, and degrees wrap round the circle
+        Self::from_arcseconds(degrees as i64 * 3600 + minutes as i64 * 60 + seconds as i64)
+    }
+    
+    /// Creates from a number of arcseconds, which may be negative or past a full circle
+    pub const fn from_arcseconds(arcseconds: i64) -> Self {
         Self {
-            degrees: total_degrees,
-            minutes: total_minutes,
-            seconds: total_seconds,
+            arcseconds: arcseconds.rem_euclid(ARCSECONDS_PER_CIRCLE as i64) as u32,
         }
     }
     
-    /// Creates from total degrees
-    pub fn from_degrees(total_degrees: f32) -> Self {
-        let normalized = normalize_degrees(total_degrees);
-        let degrees = normalized.floor() as u16;
-        let remaining_minutes = (normalized - degrees as f32) * 60.0;
-        let minutes = remaining_minutes.floor() as u8;
-        let seconds = ((remaining_minutes - minutes as f32) * 60.0).round() as u8;
-        
-        Self::new(degrees, minutes, seconds)
+    /// Creates from total degrees, rounded to the nearest second
+    pub fn from_degrees(total_degrees: f64) -> Self {
+        Self::from_arcseconds((total_degrees.rem_euclid(360.0) * 3600.0).round() as i64)
     }
     
     /// Creates from radians
-    pub fn from_radians(radians: f32) -> Self {
+    pub fn from_radians(radians: f64) -> Self {
         let degrees = radians_to_degrees(radians);
         Self::from_degrees(degrees)
     }
     
+    /// Arcseconds from 0°, in [0, 1296000)
+    pub fn arcseconds(&self) -> u32 {
+        self.arcseconds
+    }
+    
     /// Converts to total degrees
-    pub fn to_degrees(&self) -> f32 {
-        self.degrees as f32 + (self.minutes as f32 / 60.0) + (self.seconds as f32 / 3600.0)
+    pub fn to_degrees(&self) -> f64 {
+        self.arcseconds as f64 / 3600.0
     }
     
     /// Converts to radians
-    pub fn to_radians(&self) -> f32 {
+    pub fn to_radians(&self) -> f64 {
         degrees_to_radians(self.to_degrees())
     }
     
     /// Formats as string (e.g., "123°45'12"" or "123°45'" if no seconds)
     pub fn format(&self) -> String {
-        if self.seconds == 0 {
-            format!("{}°{:02}'", self.degrees, self.minutes)
+        if self.seconds() == 0 {
+            format!("{}°{:02}'", self.degrees(), self.minutes())
         } else {
-            format!("{}°{:02}'{:02}\"", self.degrees, self.minutes, self.seconds)
+            format!("{}°{:02}'{:02}\"", self.degrees(), self.minutes(), self.seconds())
         }
     }
     
     /// Adds another DegreePosition
     pub fn add(&self, other: DegreePosition) -> Self {
-        let total_degrees = self.to_degrees() + other.to_degrees();
-        Self::from_degrees(total_degrees)
+        Self::from_arcseconds(self.arcseconds as i64 + other.arcseconds as i64)
     }
     
     /// Subtracts another DegreePosition
     pub fn subtract(&self, other: DegreePosition) -> Self {
-        let total_degrees = self.to_degrees() - other.to_degrees();
-        Self::from_degrees(total_degrees)
+        Self::from_arcseconds(self.arcseconds as i64 - other.arcseconds as i64)
     }
     
     /// Interpolates between this position and another
-    pub fn interpolate(&self, other: DegreePosition, factor: f32) -> Self {
-        let start_deg = self.to_degrees();
-        let end_deg = other.to_degrees();
-        
-        // Handle crossing the 0°/360° boundary
-        let mut diff = end_deg - start_deg;
-        if diff > 180.0 {
-            diff -= 360.0;
-        } else if diff < -180.0 {
-            diff += 360.0;
+    pub fn interpolate(&self, other: DegreePosition, factor: f64) -> Self {
+        // Handle crossing the 0°/360° boundary by taking the shorter way round
+        let circle = ARCSECONDS_PER_CIRCLE as i64;
+        let mut diff = (other.arcseconds as i64 - self.arcseconds as i64).rem_euclid(circle);
+        if diff > circle / 2 {
+            diff -= circle;
         }
         
-        let result = start_deg + diff * factor;
-        Self::from_degrees(result)
+        Self::from_arcseconds(self.arcseconds as i64 + (diff as f64 * factor).round() as i64)
+    }
+    
+    /// The position on a harmonic chart, i.e. this position multiplied by the harmonic
+    pub fn to_harmonic(&self, harmonic: u32) -> Self {
+        let product = self.arcseconds as u64 * harmonic.max(1) as u64;
+        Self::from_arcseconds((product % ARCSECONDS_PER_CIRCLE as u64) as i64)
+    }
+    
+    /// The position in the first harmonic span that a harmonic position came from
+    pub fn from_harmonic(&self, harmonic: u32) -> Self {
+        Self::from_degrees(self.to_degrees() / harmonic.max(1) as f64)
     }
     
     // Getters
-    pub fn degrees(&self) -> u16 { self.degrees }
-    pub fn minutes(&self) -> u8 { self.minutes }
-    pub fn seconds(&self) -> u8 { self.seconds }
+    pub fn degrees(&self) -> u16 { (self.arcseconds / 3600) as u16 }
+    pub fn minutes(&self) -> u8 { (self.arcseconds / 60 % 60) as u8 }
+    pub fn seconds(&self) -> u8 { (self.arcseconds % 60) as u8 }
 }
 
 /// Convert an angle to its harmonic equivalent
-pub fn to_harmonic_angle(angle: f32, harmonic: u8) -> f32 {
+pub fn to_harmonic_angle(angle: f64, harmonic: u32) -> f64 {
     if harmonic <= 1 {
         return angle;
     }
     
-    normalize_degrees(angle * harmonic as f32)
+    (angle * harmonic as f64).rem_euclid(360.0)
 }
 
 /// Convert a harmonic angle back to the base angle
-pub fn from_harmonic_angle(harmonic_angle: f32, harmonic: u8) -> f32 {
+pub fn from_harmonic_angle(harmonic_angle: f64, harmonic: u32) -> f64 {
     if harmonic <= 1 {
         return harmonic_angle;
     }
     
-    normalize_degrees(harmonic_angle / harmonic as f32)
+    normalize_degrees(harmonic_angle) / harmonic as f64
 }
 
 #[cfg(test)]
@@ -197,4 +191,100 @@ mod tests {
         assert_eq!(quarter.degrees(), 22);
         assert_eq!(quarter.minutes(), 30);
     }
-}
\ No newline at end of file
+    
+    /// Deterministic pseudo-random numbers in [0, 1) for the property tests
+    fn samples(count: usize) -> impl Iterator<Item = f64> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..count).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        })
+    }
+    
+    fn assert_valid(pos: DegreePosition) {
+        assert!(pos.arcseconds() < ARCSECONDS_PER_CIRCLE);
+        assert!(pos.degrees() < 360 && pos.minutes() < 60 && pos.seconds() < 60, "{:?}", pos);
+    }
+    
+    #[test]
+    fn test_seconds_never_overflow() {
+        // These used to round up to 60 seconds
+        assert_eq!(DegreePosition::from_degrees(29.999999).format(), "30°00'");
+        assert_eq!(DegreePosition::from_degrees(359.9999999), DegreePosition::new(0, 0, 0));
+        assert_eq!(DegreePosition::from_degrees(-0.0000001), DegreePosition::new(0, 0, 0));
+        assert_eq!(DegreePosition::new(10, 59, 60).format(), "11°00'");
+        assert_eq!(DegreePosition::from_arcseconds(-1).format(), "359°59'59\"");
+    }
+    
+    #[test]
+    fn test_normalization_properties() {
+        for x in samples(10_000) {
+            let degrees = (x - 0.5) * 20_000.0;
+            let pos = DegreePosition::from_degrees(degrees);
+            assert_valid(pos);
+            // Within half a second of the angle, going the short way round
+            let diff = (pos.to_degrees() - degrees).rem_euclid(360.0);
+            assert!(diff.min(360.0 - diff) * 3600.0 <= 0.5 + 1e-6, "{} -> {:?}", degrees, pos);
+            // Converting back and forth is stable
+            assert_eq!(DegreePosition::from_degrees(pos.to_degrees()), pos);
+            assert_eq!(DegreePosition::from_radians(pos.to_radians()), pos);
+            assert_eq!(DegreePosition::new(pos.degrees(), pos.minutes(), pos.seconds()), pos);
+        }
+    }
+    
+    #[test]
+    fn test_add_subtract_properties() {
+        let mut values = samples(4_000).map(|x| DegreePosition::from_degrees(x * 360.0));
+        while let (Some(a), Some(b)) = (values.next(), values.next()) {
+            let sum = a.add(b);
+            assert_valid(sum);
+            assert_eq!(sum, b.add(a));
+            assert_eq!(sum.subtract(b), a);
+            assert_eq!(a.subtract(b).add(b), a);
+            assert_eq!(a.subtract(a), DegreePosition::new(0, 0, 0));
+            assert_eq!(sum.arcseconds() as u64, (a.arcseconds() as u64 + b.arcseconds() as u64) % 1_296_000);
+        }
+        
+        // Stepping a second at a time round the whole circle comes back exactly
+        let second = DegreePosition::new(0, 0, 1);
+        let start = DegreePosition::new(359, 59, 58);
+        let mut pos = start;
+        for _ in 0..ARCSECONDS_PER_CIRCLE {
+            pos = pos.add(second);
+            assert_valid(pos);
+        }
+        assert_eq!(pos, start);
+        assert_eq!(start.add(second).add(second), DegreePosition::new(0, 0, 0));
+    }
+    
+    #[test]
+    fn test_harmonic_properties() {
+        let values: Vec<DegreePosition> = samples(2_000).map(|x| DegreePosition::from_degrees(x * 360.0)).collect();
+        for (pair, harmonic) in values.chunks(2).zip(0u32..) {
+            let (a, b) = (pair[0], pair[1]);
+            let harmonic = harmonic % 180 + 1;
+            let ha = a.to_harmonic(harmonic);
+            assert_valid(ha);
+            // Exact: the harmonic of a sum is the sum of the harmonics
+            assert_eq!(a.add(b).to_harmonic(harmonic), ha.add(b.to_harmonic(harmonic)));
+            // Agrees with the floating point conversion to the nearest second
+            assert_eq!(ha, DegreePosition::from_degrees(to_harmonic_angle(a.to_degrees(), harmonic)));
+            
+            // Going back lands in the first span, a whole number of spans from the start
+            let base = ha.from_harmonic(harmonic);
+            assert_valid(base);
+            assert!(base.to_degrees() <= 360.0 / harmonic as f64);
+            let span = ARCSECONDS_PER_CIRCLE as f64 / harmonic as f64;
+            let offset = a.subtract(base).arcseconds() as f64 % span;
+            assert!(offset.min(span - offset) <= 0.5 + 1e-6, "{:?} {} {:?}", a, harmonic, base);
+            // and back again to within half a second per harmonic
+            let back = base.to_harmonic(harmonic).subtract(ha).arcseconds();
+            assert!(back.min(ARCSECONDS_PER_CIRCLE - back) as f64 <= harmonic as f64 / 2.0);
+        }
+        assert_eq!(DegreePosition::new(90, 0, 0).to_harmonic(4), DegreePosition::new(0, 0, 0));
+        assert_eq!(DegreePosition::new(120, 0, 0).from_harmonic(4), DegreePosition::new(30, 0, 0));
+        assert_eq!(DegreePosition::new(123, 0, 0).to_harmonic(0), DegreePosition::new(123, 0, 0));
+    }
+}
@@ -0,0 +1,108 @@
+//! Antiscia and contra-antiscia: points mirrored across the solstice axis,
+//! 0° Cancer to 0° Capricorn, and across the equinox axis, 0° Aries to 0° Libra.
+
+use crate::astrology::chart::ChartPoint;
+
+/// Which axis a point is mirrored across
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mirror {
+    /// Across the solstice axis, so 10° Gemini mirrors to 20° Cancer
+    Antiscion,
+    /// Across the equinox axis, so 10° Gemini mirrors to 20° Capricorn
+    ContraAntiscion,
+}
+
+impl Mirror {
+    pub const ALL: [Mirror; 2] = [Mirror::Antiscion, Mirror::ContraAntiscion];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Antiscion => "Antiscion",
+            Self::ContraAntiscion => "Contra-antiscion",
+        }
+    }
+
+    /// Short mark put after a mirrored point, e.g. "SU(A)"
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Antiscion => "A",
+            Self::ContraAntiscion => "CA",
+        }
+    }
+
+    /// Longitude of the axis the mirror turns about
+    pub fn axis(&self) -> f64 {
+        match self {
+            Self::Antiscion => 90.0,
+            Self::ContraAntiscion => 0.0,
+        }
+    }
+
+    /// Mirrors a longitude in degrees
+    pub fn reflect(&self, longitude: f64) -> f64 {
+        reflect_across(longitude, self.axis())
+    }
+
+    /// Mirrors a chart point, which then moves the other way round the zodiac
+    pub fn reflect_point(&self, point: &ChartPoint) -> ChartPoint {
+        ChartPoint {
+            id: point.id,
+            longitude: self.reflect(point.longitude),
+            latitude: point.latitude,
+            speed: -point.speed,
+        }
+    }
+}
+
+/// Mirrors a longitude across the axis through another longitude and its opposite
+pub fn reflect_across(longitude: f64, axis: f64) -> f64 {
+    (2.0 * axis - longitude).rem_euclid(360.0)
+}
+
+/// The antiscion of a longitude: its mirror across 0° Cancer
+pub fn antiscion(longitude: f64) -> f64 {
+    Mirror::Antiscion.reflect(longitude)
+}
+
+/// The contra-antiscion of a longitude: its mirror across 0° Aries
+pub fn contra_antiscion(longitude: f64) -> f64 {
+    Mirror::ContraAntiscion.reflect(longitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::PointId;
+    use crate::astrology::Body;
+
+    #[test]
+    fn test_antiscia() {
+        // 10° Gemini mirrors to 20° Cancer and 20° Capricorn
+        assert_eq!(antiscion(70.0), 110.0);
+        assert_eq!(contra_antiscion(70.0), 290.0);
+        // The axes themselves stay put
+        assert_eq!(antiscion(90.0), 90.0);
+        assert_eq!(antiscion(270.0), 270.0);
+        assert_eq!(contra_antiscion(0.0), 0.0);
+        // Antiscion and contra-antiscion are always opposite
+        for longitude in [0.0, 13.5, 181.0, 359.9] {
+            let apart = (antiscion(longitude) - contra_antiscion(longitude)).rem_euclid(360.0);
+            assert!((apart - 180.0).abs() < 1e-9);
+        }
+        assert_eq!(reflect_across(100.0, 350.0), 240.0);
+    }
+
+    #[test]
+    fn test_reflect_point() {
+        let point = ChartPoint {
+            id: PointId::Body(Body::Venus),
+            longitude: 200.0,
+            latitude: 1.0,
+            speed: 1.2,
+        };
+        let mirrored = Mirror::Antiscion.reflect_point(&point);
+        assert_eq!(mirrored.longitude, 340.0);
+        assert_eq!(mirrored.speed, -1.2);
+        assert_eq!(mirrored.id, point.id);
+    }
+}
@@ -0,0 +1,413 @@
+//! Aspects between chart points: the classical major and minor aspects and
+//! the arms of a harmonic dial, with orbs and applying/separating status.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::astrology::antiscia::Mirror;
+use crate::astrology::chart::{ChartPoint, PointId};
+use crate::astrology::constants::{DEFAULT_ORB, MAJOR_ASPECTS, MINOR_ASPECTS};
+use crate::astrology::Harmonic;
+
+/// Separations closer than this (degrees) count as the same aspect angle
+const EPSILON: f64 = 1e-9;
+
+/// Where an aspect angle comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AspectSource {
+    Major,
+    Minor,
+    /// An arm of a harmonic dial
+    Harmonic(Harmonic),
+}
+
+/// An aspect angle and its name
+#[derive(Debug, Clone, PartialEq)]
+pub struct AspectType {
+    /// Angle in degrees, 0 to 180
+    pub angle: f64,
+    pub name: String,
+    pub source: AspectSource,
+}
+
+impl AspectType {
+    /// The major aspects from `MAJOR_ASPECTS`
+    pub fn major() -> Vec<Self> {
+        MAJOR_ASPECTS
+            .iter()
+            .map(|&(angle, name)| Self {
+                angle: angle as f64,
+                name: name.to_string(),
+                source: AspectSource::Major,
+            })
+            .collect()
+    }
+
+    /// The minor aspects from `MINOR_ASPECTS`
+    pub fn minor() -> Vec<Self> {
+        MINOR_ASPECTS
+            .iter()
+            .map(|&(angle, name)| Self {
+                angle: angle as f64,
+                name: name.to_string(),
+                source: AspectSource::Minor,
+            })
+            .collect()
+    }
+
+    /// One aspect per arm of a harmonic dial up to the opposition, named by
+    /// its fraction of the circle, e.g. "3/16" for 67°30'
+    pub fn harmonic(harmonic: Harmonic) -> Vec<Self> {
+        let arms = harmonic.arm_count() as u64;
+        (0..=arms / 2)
+            .map(|k| {
+                let divisor = gcd(k, arms);
+                let name = if k == 0 {
+                    "0".to_string()
+                } else {
+                    format!("{}/{}", k / divisor, arms / divisor)
+                };
+                Self {
+                    angle: k as f64 * 360.0 / arms as f64,
+                    name,
+                    source: AspectSource::Harmonic(harmonic),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether the aspect belongs to the hard, fourth-harmonic family:
+    /// conjunction, semi-square, square, sesquiquadrate and opposition
+    pub fn is_hard(&self) -> bool {
+        let eighths = self.angle / 45.0;
+        (eighths - eighths.round()).abs() < EPSILON
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Orbs in degrees for each aspect and point.
+///
+/// A point orb is given for aspects at the default orb and scales other
+/// aspects in proportion; where both points have one the wider counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orbs {
+    pub default: f64,
+    aspects: HashMap<String, f64>,
+    points: HashMap<PointId, f64>,
+}
+
+impl Default for Orbs {
+    fn default() -> Self {
+        Self::new(DEFAULT_ORB as f64)
+    }
+}
+
+impl Orbs {
+    pub fn new(default: f64) -> Self {
+        Self {
+            default,
+            aspects: HashMap::new(),
+            points: HashMap::new(),
+        }
+    }
+
+    /// Sets the orb of an aspect by name, e.g. "Square"
+    pub fn set_aspect_orb(&mut self, name: impl Into<String>, orb: f64) {
+        self.aspects.insert(name.into(), orb);
+    }
+
+    /// Sets the orb of a point, e.g. wider for the Sun and Moon
+    pub fn set_point_orb(&mut self, point: PointId, orb: f64) {
+        self.points.insert(point, orb);
+    }
+
+    /// Returns the orb for an aspect between two points
+    pub fn orb(&self, aspect: &AspectType, first: PointId, second: PointId) -> f64 {
+        let aspect_orb = self.aspects.get(&aspect.name).copied().unwrap_or(self.default);
+        let point_orb = [first, second]
+            .iter()
+            .filter_map(|point| self.points.get(point).copied())
+            .reduce(f64::max);
+        match point_orb {
+            Some(orb) if self.default > 0.0 => aspect_orb * orb / self.default,
+            _ => aspect_orb,
+        }
+    }
+}
+
+/// Whether the aspect is growing more or less exact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AspectMotion {
+    Applying,
+    Separating,
+    /// Exact, or the points do not move relative to each other
+    Stationary,
+}
+
+/// An aspect found between two chart points
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aspect {
+    pub first: PointId,
+    pub second: PointId,
+    pub aspect: AspectType,
+    /// Angular distance between the points, 0 to 180 degrees
+    pub separation: f64,
+    /// Distance from the exact aspect in degrees
+    pub orb: f64,
+    pub motion: AspectMotion,
+    /// Set when the aspect is made by the mirror image of the first point
+    pub mirror: Option<Mirror>,
+}
+
+impl Aspect {
+    /// Returns the pair and aspect, e.g. "SU Square MO", or "SU(A) Square MO"
+    /// for the Sun's antiscion
+    pub fn label(&self) -> String {
+        let mirror = self.mirror.map_or(String::new(), |mirror| format!("({})", mirror.abbreviation()));
+        format!(
+            "{}{} {} {}",
+            self.first.abbreviation(),
+            mirror,
+            self.aspect.name,
+            self.second.abbreviation()
+        )
+    }
+}
+
+/// Finds aspects between every pair of points
+#[derive(Debug, Clone, PartialEq)]
+pub struct AspectEngine {
+    pub aspects: Vec<AspectType>,
+    pub orbs: Orbs,
+    /// Also look for aspects to the antiscia of the points
+    pub antiscia: bool,
+}
+
+impl Default for AspectEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AspectEngine {
+    /// An engine for the major and minor aspects at the default orbs
+    pub fn new() -> Self {
+        let mut aspects = AspectType::major();
+        aspects.extend(AspectType::minor());
+        Self {
+            aspects,
+            orbs: Orbs::default(),
+            antiscia: false,
+        }
+    }
+
+    /// Adds the arms of a harmonic dial, skipping angles already covered
+    pub fn with_harmonic(mut self, harmonic: Harmonic) -> Self {
+        for arm in AspectType::harmonic(harmonic) {
+            if !self.aspects.iter().any(|aspect| (aspect.angle - arm.angle).abs() < EPSILON) {
+                self.aspects.push(arm);
+            }
+        }
+        self
+    }
+
+    /// Returns the aspects between all pairs of points, closest first.
+    ///
+    /// Each pair gets at most one aspect, the one nearest exact within its orb.
+    /// With antiscia on, the antiscion of the first point of each pair is tried
+    /// as well; mirroring the second instead would find the same. The
+    /// contra-antiscion lies opposite the antiscion, so it would only repeat
+    /// each contact as the opposite aspect and is left out.
+    pub fn find(&self, points: &[ChartPoint]) -> Vec<Aspect> {
+        let mut found = Vec::new();
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                found.extend(self.between(a, b));
+                if self.antiscia {
+                    let mirror = Mirror::Antiscion;
+                    found.extend(self.between(&mirror.reflect_point(a), b).map(|aspect| Aspect {
+                        mirror: Some(mirror),
+                        ..aspect
+                    }));
+                }
+            }
+        }
+        found.sort_by(|a, b| a.orb.partial_cmp(&b.orb).unwrap_or(Ordering::Equal));
+        found
+    }
+
+    /// Returns the closest aspect between two points within orb
+    pub fn between(&self, a: &ChartPoint, b: &ChartPoint) -> Option<Aspect> {
+        // Signed distance from a to b in [-180, 180)
+        let difference = (b.longitude - a.longitude + 180.0).rem_euclid(360.0) - 180.0;
+        let separation = difference.abs();
+
+        let (aspect, deviation) = self
+            .aspects
+            .iter()
+            .map(|aspect| (aspect, separation - aspect.angle))
+            .filter(|(aspect, deviation)| deviation.abs() <= self.orbs.orb(aspect, a.id, b.id))
+            .min_by(|x, y| x.1.abs().partial_cmp(&y.1.abs()).unwrap_or(Ordering::Equal))?;
+
+        // The separation changes at the relative speed, in the direction of the difference
+        let widening = difference.signum() * (b.speed - a.speed);
+        let tightening = -deviation.signum() * widening;
+        let motion = if deviation.abs() < EPSILON || widening.abs() < EPSILON {
+            AspectMotion::Stationary
+        } else if tightening > 0.0 {
+            AspectMotion::Applying
+        } else {
+            AspectMotion::Separating
+        };
+
+        Some(Aspect {
+            first: a.id,
+            second: b.id,
+            aspect: aspect.clone(),
+            separation,
+            orb: deviation.abs(),
+            motion,
+            mirror: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::Body;
+
+    #[test]
+    fn test_major_aspects() {
+        let engine = AspectEngine::new();
+        let sun = ChartPoint::test_point(PointId::Body(Body::Sun), 10.0).with_speed(1.0);
+        let moon = ChartPoint::test_point(PointId::Body(Body::Moon), 101.5).with_speed(13.0);
+        let aspect = engine.between(&sun, &moon).unwrap();
+        assert_eq!(aspect.aspect.name, "Square");
+        assert!((aspect.orb - 1.5).abs() < 1e-9);
+        assert_eq!(aspect.label(), "SU Square MO");
+
+        // Across 0° Aries
+        let mars = ChartPoint::test_point(PointId::Body(Body::Mars), 355.0).with_speed(0.5);
+        let jupiter = ChartPoint::test_point(PointId::Body(Body::Jupiter), 174.0).with_speed(0.1);
+        assert_eq!(engine.between(&mars, &jupiter).unwrap().aspect.name, "Opposition");
+
+        let far = ChartPoint::test_point(PointId::Body(Body::Saturn), 110.0);
+        assert!(engine.between(&sun, &far).is_none());
+    }
+
+    #[test]
+    fn test_applying_and_separating() {
+        let engine = AspectEngine::new();
+        // The faster Moon moves on past the exact square
+        let sun = ChartPoint::test_point(PointId::Body(Body::Sun), 10.0).with_speed(1.0);
+        let moon = ChartPoint::test_point(PointId::Body(Body::Moon), 101.0).with_speed(13.0);
+        assert_eq!(engine.between(&sun, &moon).unwrap().motion, AspectMotion::Separating);
+
+        let moon = ChartPoint::test_point(PointId::Body(Body::Moon), 99.0).with_speed(13.0);
+        assert_eq!(engine.between(&sun, &moon).unwrap().motion, AspectMotion::Applying);
+
+        // A retrograde planet backing into a conjunction applies
+        let mercury = ChartPoint::test_point(PointId::Body(Body::Mercury), 11.0).with_speed(-0.5);
+        assert_eq!(engine.between(&sun, &mercury).unwrap().motion, AspectMotion::Applying);
+
+        // Approaching the opposition widens the separation towards 180°
+        let mars = ChartPoint::test_point(PointId::Body(Body::Mars), 189.0);
+        let sun_before = ChartPoint::test_point(PointId::Body(Body::Sun), 10.5).with_speed(1.0);
+        assert_eq!(engine.between(&sun_before, &mars).unwrap().motion, AspectMotion::Separating);
+        let sun_after = ChartPoint::test_point(PointId::Body(Body::Sun), 8.5).with_speed(1.0);
+        assert_eq!(engine.between(&sun_after, &mars).unwrap().motion, AspectMotion::Applying);
+
+        let fixed = ChartPoint::test_point(PointId::Body(Body::Saturn), 190.0);
+        assert_eq!(engine.between(&mars, &fixed).unwrap().motion, AspectMotion::Stationary);
+    }
+
+    #[test]
+    fn test_orbs() {
+        let mut engine = AspectEngine::new();
+        let sun = ChartPoint::test_point(PointId::Body(Body::Sun), 0.0).with_speed(1.0);
+        let moon = ChartPoint::test_point(PointId::Body(Body::Moon), 63.0).with_speed(13.0);
+        assert!(engine.between(&sun, &moon).is_none());
+
+        engine.orbs.set_aspect_orb("Sextile", 4.0);
+        assert_eq!(engine.between(&sun, &moon).unwrap().aspect.name, "Sextile");
+
+        // A point orb of 4° doubles every aspect's orb, as the default is 2°
+        let mut engine = AspectEngine::new();
+        engine.orbs.set_point_orb(PointId::Body(Body::Sun), 4.0);
+        engine.orbs.set_aspect_orb("Trine", 1.0);
+        let venus = ChartPoint::test_point(PointId::Body(Body::Venus), 121.8).with_speed(1.0);
+        assert!(engine.between(&sun, &venus).is_some());
+        let mars = ChartPoint::test_point(PointId::Body(Body::Mars), 0.0).with_speed(1.0);
+        assert!(engine.between(&mars, &venus).is_none());
+    }
+
+    #[test]
+    fn test_harmonic_aspects() {
+        let names: Vec<String> = AspectType::harmonic(Harmonic::Fourth)
+            .into_iter()
+            .map(|aspect| aspect.name)
+            .collect();
+        assert_eq!(names, ["0", "1/16", "1/8", "3/16", "1/4", "5/16", "3/8", "7/16", "1/2"]);
+
+        let engine = AspectEngine::new().with_harmonic(Harmonic::Fourth);
+        let sun = ChartPoint::test_point(PointId::Body(Body::Sun), 0.0).with_speed(1.0);
+        let pluto = ChartPoint::test_point(PointId::Body(Body::Pluto), 67.0);
+        assert_eq!(engine.between(&sun, &pluto).unwrap().aspect.name, "3/16");
+
+        // Arms that are also classical aspects keep their names
+        let moon = ChartPoint::test_point(PointId::Body(Body::Moon), 90.5).with_speed(13.0);
+        assert_eq!(engine.between(&sun, &moon).unwrap().aspect.name, "Square");
+        assert!(AspectEngine::new().between(&sun, &pluto).is_none());
+    }
+
+    #[test]
+    fn test_find_all_pairs() {
+        let points: Vec<ChartPoint> = Body::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, &body)| ChartPoint::test_point(PointId::Body(body), i as f64 * 30.0))
+            .collect();
+        let aspects = AspectEngine::new().find(&points);
+        assert!(aspects.iter().all(|aspect| aspect.orb < 1e-9));
+        // Points 30° apart pair up in every multiple of 30° that is an aspect
+        assert!(aspects.iter().any(|aspect| aspect.aspect.name == "Trine"));
+        assert!(aspects.iter().all(|aspect| aspect.first != aspect.second));
+    }
+
+    #[test]
+    fn test_antiscia() {
+        // 10° Gemini and 20° Cancer are antiscia of each other
+        let venus = ChartPoint::test_point(PointId::Body(Body::Venus), 70.0).with_speed(1.0);
+        let mars = ChartPoint::test_point(PointId::Body(Body::Mars), 110.5).with_speed(0.5);
+        let mut engine = AspectEngine::new();
+        assert!(engine.find(&[venus, mars]).is_empty());
+
+        engine.antiscia = true;
+        let found = engine.find(&[venus, mars]);
+        // The contra-antiscion at 20° Capricorn is opposite Mars, which says the same
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].label(), "VE(A) Conjunction MA");
+        assert!((found[0].orb - 0.5).abs() < 1e-9);
+
+        // From 10° Sagittarius the contra-antiscion is conjunct Mars and the antiscion opposite
+        let venus = ChartPoint::test_point(PointId::Body(Body::Venus), 250.0);
+        let found = engine.find(&[venus, mars]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].label(), "VE(A) Opposition MA");
+    }
+
+    #[test]
+    fn test_is_hard() {
+        assert!(AspectType::major()[2].is_hard());
+        assert!(!AspectType::major()[1].is_hard());
+        assert!(AspectType::minor()[1].is_hard());
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+/// A celestial body that can be placed on the dial
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Body {
+    Sun,
+    Moon,
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    Pluto,
+    Cupido,
+    Hades,
+    Zeus,
+    Kronos,
+    Apollon,
+    Admetos,
+    Vulkanus,
+    Poseidon,
+}
+
+impl Body {
+    /// The ten classical bodies, Sun through Pluto
+    pub const CLASSICAL: [Body; 10] = [
+        Self::Sun,
+        Self::Moon,
+        Self::Mercury,
+        Self::Venus,
+        Self::Mars,
+        Self::Jupiter,
+        Self::Saturn,
+        Self::Uranus,
+        Self::Neptune,
+        Self::Pluto,
+    ];
+
+    /// The eight transneptunian points of the Hamburg School
+    pub const TRANSNEPTUNIANS: [Body; 8] = [
+        Self::Cupido,
+        Self::Hades,
+        Self::Zeus,
+        Self::Kronos,
+        Self::Apollon,
+        Self::Admetos,
+        Self::Vulkanus,
+        Self::Poseidon,
+    ];
+
+    /// Every body, classical planets first
+    pub const ALL: [Body; 18] = [
+        Self::Sun,
+        Self::Moon,
+        Self::Mercury,
+        Self::Venus,
+        Self::Mars,
+        Self::Jupiter,
+        Self::Saturn,
+        Self::Uranus,
+        Self::Neptune,
+        Self::Pluto,
+        Self::Cupido,
+        Self::Hades,
+        Self::Zeus,
+        Self::Kronos,
+        Self::Apollon,
+        Self::Admetos,
+        Self::Vulkanus,
+        Self::Poseidon,
+    ];
+
+    /// Whether the body is one of the hypothetical Uranian points
+    pub fn is_transneptunian(&self) -> bool {
+        Self::TRANSNEPTUNIANS.contains(self)
+    }
+
+    /// Returns the full name of the body
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sun => "Sun",
+            Self::Moon => "Moon",
+            Self::Mercury => "Mercury",
+            Self::Venus => "Venus",
+            Self::Mars => "Mars",
+            Self::Jupiter => "Jupiter",
+            Self::Saturn => "Saturn",
+            Self::Uranus => "Uranus",
+            Self::Neptune => "Neptune",
+            Self::Pluto => "Pluto",
+            Self::Cupido => "Cupido",
+            Self::Hades => "Hades",
+            Self::Zeus => "Zeus",
+            Self::Kronos => "Kronos",
+            Self::Apollon => "Apollon",
+            Self::Admetos => "Admetos",
+            Self::Vulkanus => "Vulkanus",
+            Self::Poseidon => "Poseidon",
+        }
+    }
+
+    /// Returns the two-letter abbreviation used in Uranian notation
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Sun => "SU",
+            Self::Moon => "MO",
+            Self::Mercury => "ME",
+            Self::Venus => "VE",
+            Self::Mars => "MA",
+            Self::Jupiter => "JU",
+            Self::Saturn => "SA",
+            Self::Uranus => "UR",
+            Self::Neptune => "NE",
+            Self::Pluto => "PL",
+            Self::Cupido => "CU",
+            Self::Hades => "HA",
+            Self::Zeus => "ZE",
+            Self::Kronos => "KR",
+            Self::Apollon => "AP",
+            Self::Admetos => "AD",
+            Self::Vulkanus => "VU",
+            Self::Poseidon => "PO",
+        }
+    }
+
+    /// Returns the astronomical symbol, if the body has one in Unicode
+    pub fn glyph(&self) -> Option<&'static str> {
+        match self {
+            Self::Sun => Some("\u{2609}"),
+            Self::Moon => Some("\u{263D}"),
+            Self::Mercury => Some("\u{263F}"),
+            Self::Venus => Some("\u{2640}"),
+            Self::Mars => Some("\u{2642}"),
+            Self::Jupiter => Some("\u{2643}"),
+            Self::Saturn => Some("\u{2644}"),
+            Self::Uranus => Some("\u{2645}"),
+            Self::Neptune => Some("\u{2646}"),
+            Self::Pluto => Some("\u{2647}"),
+            // The transneptunian symbols are not encoded
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abbreviations_are_unique() {
+        for (i, a) in Body::ALL.iter().enumerate() {
+            for b in &Body::ALL[i + 1..] {
+                assert_ne!(a.abbreviation(), b.abbreviation());
+            }
+        }
+    }
+
+    #[test]
+    fn test_transneptunians() {
+        assert!(Body::Cupido.is_transneptunian());
+        assert!(Body::Poseidon.is_transneptunian());
+        assert!(!Body::Pluto.is_transneptunian());
+        assert_eq!(Body::ALL[10..], Body::TRANSNEPTUNIANS);
+    }
+
+    #[test]
+    fn test_glyphs() {
+        assert!(Body::CLASSICAL.iter().all(|body| body.glyph().is_some()));
+        assert!(Body::TRANSNEPTUNIANS.iter().all(|body| body.glyph().is_none()));
+        assert_eq!(Body::Sun.glyph(), Some("☉"));
+    }
+}
@@ -0,0 +1,468 @@
+//! Natal charts: birth data together with the positions calculated from it.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::astrology::houses::{self, HouseError, HouseFrame, HouseSystem, Houses};
+use crate::astrology::{ephemeris, time, Body, DegreePosition};
+
+/// Half-width in days of the interval used to derive the node's daily motion
+const SPEED_STEP: f64 = 0.5;
+
+/// Half-width in days for the angles, which turn once a day: one minute
+const ANGLE_SPEED_STEP: f64 = 1.0 / 1440.0;
+
+/// A point that can be placed on the dial: a body or a personal point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PointId {
+    Body(Body),
+    /// 0° Aries, the Uranian "Widderpunkt"
+    AriesPoint,
+    Midheaven,
+    Ascendant,
+    /// Mean ascending node of the Moon
+    Node,
+}
+
+impl PointId {
+    /// Returns the full name of the point
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Body(body) => body.name(),
+            Self::AriesPoint => "Aries Point",
+            Self::Midheaven => "Midheaven",
+            Self::Ascendant => "Ascendant",
+            Self::Node => "Node",
+        }
+    }
+
+    /// Returns the two-letter abbreviation used in Uranian notation
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Body(body) => body.abbreviation(),
+            Self::AriesPoint => "AR",
+            Self::Midheaven => "MC",
+            Self::Ascendant => "AS",
+            Self::Node => "NO",
+        }
+    }
+
+    /// Returns the symbol drawn on the dial, if the point has one in Unicode
+    pub fn glyph(&self) -> Option<&'static str> {
+        match self {
+            Self::Body(body) => body.glyph(),
+            Self::AriesPoint => Some("\u{2648}"),
+            Self::Node => Some("\u{260A}"),
+            Self::Midheaven | Self::Ascendant => None,
+        }
+    }
+
+    /// Looks up a point by its abbreviation, ignoring case
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Self> {
+        Body::ALL
+            .iter()
+            .map(|&body| Self::Body(body))
+            .chain([Self::AriesPoint, Self::Midheaven, Self::Ascendant, Self::Node])
+            .find(|point| point.abbreviation().eq_ignore_ascii_case(abbreviation))
+    }
+
+    /// Whether the point is one of the hypothetical transneptunian points
+    pub fn is_transneptunian(&self) -> bool {
+        matches!(self, Self::Body(body) if body.is_transneptunian())
+    }
+}
+
+/// A calculated chart position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChartPoint {
+    pub id: PointId,
+    /// Ecliptic longitude in degrees [0, 360)
+    pub longitude: f64,
+    /// Ecliptic latitude in degrees
+    pub latitude: f64,
+    /// Daily motion in longitude, degrees per day
+    pub speed: f64,
+}
+
+impl ChartPoint {
+    /// Returns the longitude as a dial position
+    pub fn position(&self) -> DegreePosition {
+        DegreePosition::from_degrees(self.longitude)
+    }
+}
+
+#[cfg(test)]
+impl ChartPoint {
+    /// A stationary point on the ecliptic, for tests
+    pub(crate) fn test_point(id: PointId, longitude: f64) -> Self {
+        Self {
+            id,
+            longitude,
+            latitude: 0.0,
+            speed: 0.0,
+        }
+    }
+
+    /// The same point moving at a daily speed in longitude
+    pub(crate) fn with_speed(self, speed: f64) -> Self {
+        Self { speed, ..self }
+    }
+}
+
+/// When and where a chart is cast
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedBirthData")]
+pub struct BirthData {
+    /// Local civil time, carrying its offset from UTC
+    pub datetime: DateTime<FixedOffset>,
+    /// Geographic latitude in degrees, north positive
+    pub latitude: f64,
+    /// Geographic longitude in degrees, east positive
+    pub longitude: f64,
+}
+
+impl BirthData {
+    /// Creates birth data, checking that the coordinates are on the globe
+    pub fn new(datetime: DateTime<FixedOffset>, latitude: f64, longitude: f64) -> Result<Self, ChartError> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(ChartError::InvalidLocation { latitude, longitude });
+        }
+        Ok(Self {
+            datetime,
+            latitude,
+            longitude,
+        })
+    }
+
+    /// Julian Day of the birth moment in Universal Time
+    pub fn julian_day(&self) -> f64 {
+        time::julian_day(&self.datetime)
+    }
+
+    /// The offset from UTC in effect at birth
+    pub fn timezone(&self) -> FixedOffset {
+        *self.datetime.offset()
+    }
+}
+
+/// Birth data as read from a file, before its location is checked
+#[derive(Deserialize)]
+struct UncheckedBirthData {
+    datetime: DateTime<FixedOffset>,
+    latitude: f64,
+    longitude: f64,
+}
+
+impl TryFrom<UncheckedBirthData> for BirthData {
+    type Error = ChartError;
+
+    fn try_from(data: UncheckedBirthData) -> Result<Self, ChartError> {
+        Self::new(data.datetime, data.latitude, data.longitude)
+    }
+}
+
+/// Errors raised while building, saving or loading a chart
+#[derive(Debug)]
+pub enum ChartError {
+    InvalidLocation { latitude: f64, longitude: f64 },
+    Io(std::io::Error),
+    Format(serde_json::Error),
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLocation { latitude, longitude } => {
+                write!(f, "invalid location {}, {}", latitude, longitude)
+            }
+            Self::Io(err) => write!(f, "could not access chart file: {}", err),
+            Self::Format(err) => write!(f, "invalid chart file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ChartError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidLocation { .. } => None,
+            Self::Io(err) => Some(err),
+            Self::Format(err) => Some(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for ChartError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ChartError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Format(err)
+    }
+}
+
+/// A chart with its birth data and calculated positions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chart {
+    pub name: String,
+    pub birth: BirthData,
+    points: Vec<ChartPoint>,
+}
+
+impl Chart {
+    /// Casts a chart, calculating every body and personal point
+    pub fn new(name: impl Into<String>, birth: BirthData) -> Self {
+        let points = calculate_points(&birth);
+        Self {
+            name: name.into(),
+            birth,
+            points,
+        }
+    }
+
+    /// A chart of positions derived from another, such as directed or progressed ones
+    pub fn from_points(name: impl Into<String>, birth: BirthData, points: Vec<ChartPoint>) -> Self {
+        Self {
+            name: name.into(),
+            birth,
+            points,
+        }
+    }
+
+    /// All calculated points, bodies first
+    pub fn points(&self) -> &[ChartPoint] {
+        &self.points
+    }
+
+    /// Looks up a single point
+    pub fn point(&self, id: PointId) -> Option<&ChartPoint> {
+        self.points.iter().find(|point| point.id == id)
+    }
+
+    /// Returns the dial position of a point
+    pub fn position(&self, id: PointId) -> Option<DegreePosition> {
+        self.point(id).map(ChartPoint::position)
+    }
+
+    /// Calculates the houses of a system for the birth moment and place
+    pub fn houses(&self, system: HouseSystem) -> Result<Houses, HouseError> {
+        houses::calculate(system, &HouseFrame::for_birth(&self.birth)?, &self.points)
+    }
+
+    /// Recalculates the positions, e.g. after the birth data was edited
+    pub fn recalculate(&mut self) {
+        self.points = calculate_points(&self.birth);
+    }
+
+    /// Serializes the chart as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, ChartError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Reads a chart from JSON
+    pub fn from_json(json: &str) -> Result<Self, ChartError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Saves the chart to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ChartError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Loads a chart from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ChartError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+fn calculate_points(birth: &BirthData) -> Vec<ChartPoint> {
+    let jd = birth.julian_day();
+    let jde = time::julian_ephemeris_day(jd);
+
+    let mut points: Vec<ChartPoint> = Body::ALL
+        .iter()
+        .map(|&body| {
+            let position = ephemeris::calculate(body, jde);
+            ChartPoint {
+                id: PointId::Body(body),
+                longitude: position.longitude,
+                latitude: position.latitude,
+                speed: position.speed,
+            }
+        })
+        .collect();
+
+    let frame_at = |jd: f64| HouseFrame {
+        ramc: time::local_apparent_sidereal_time(jd, birth.longitude),
+        obliquity: time::obliquity(jd),
+        latitude: birth.latitude,
+    };
+    let midheaven_at = |jd: f64| frame_at(jd).midheaven();
+    let node_at = |jd: f64| ephemeris::mean_node(time::julian_ephemeris_day(jd));
+
+    points.push(ChartPoint {
+        id: PointId::AriesPoint,
+        longitude: 0.0,
+        latitude: 0.0,
+        speed: 0.0,
+    });
+    points.push(moving_point(PointId::Midheaven, midheaven_at, jd, ANGLE_SPEED_STEP));
+    // There is no Ascendant at the poles
+    if let Ok(ascendant) = frame_at(jd).ascendant() {
+        let ascendant_at = |jd: f64| frame_at(jd).ascendant().unwrap_or(ascendant);
+        points.push(moving_point(PointId::Ascendant, ascendant_at, jd, ANGLE_SPEED_STEP));
+    }
+    points.push(moving_point(PointId::Node, node_at, jd, SPEED_STEP));
+    points
+}
+
+/// A point on the ecliptic whose daily motion is found by central difference
+fn moving_point(id: PointId, longitude_at: impl Fn(f64) -> f64, jd: f64, step: f64) -> ChartPoint {
+    let motion = longitude_at(jd + step) - longitude_at(jd - step);
+    ChartPoint {
+        id,
+        longitude: longitude_at(jd),
+        latitude: 0.0,
+        speed: ((motion + 180.0).rem_euclid(360.0) - 180.0) / (2.0 * step),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample_chart() -> Chart {
+        // 2000 Jan 1, 13:00 CET in Hamburg
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let datetime = offset.with_ymd_and_hms(2000, 1, 1, 13, 0, 0).unwrap();
+        let birth = BirthData::new(datetime, 53.55, 10.0).unwrap();
+        Chart::new("Sample", birth)
+    }
+
+    #[test]
+    fn test_chart_points() {
+        let chart = sample_chart();
+        assert_eq!(chart.points().len(), Body::ALL.len() + 4);
+        assert_eq!(chart.birth.julian_day(), ephemeris::J2000);
+
+        // The Sun was at 280°22' at J2000.0
+        let sun = chart.point(PointId::Body(Body::Sun)).unwrap();
+        assert!((sun.longitude - 280.37).abs() < 0.01);
+        assert_eq!(chart.position(PointId::AriesPoint).unwrap().degrees(), 0);
+    }
+
+    #[test]
+    fn test_personal_points() {
+        let chart = sample_chart();
+        let mc = chart.point(PointId::Midheaven).unwrap().longitude;
+        let asc = chart.point(PointId::Ascendant).unwrap().longitude;
+        // Around noon the Sun culminates near the MC and the Ascendant lies roughly
+        // a quadrant ahead of it
+        assert!((mc - 280.0).abs() < 10.0);
+        let separation = (asc - mc).rem_euclid(360.0);
+        assert!(separation > 45.0 && separation < 135.0);
+
+        // The angles turn through the zodiac once a sidereal day, unevenly
+        let mc_speed = chart.point(PointId::Midheaven).unwrap().speed;
+        assert!(mc_speed > 300.0 && mc_speed < 420.0);
+
+        let node = chart.point(PointId::Node).unwrap();
+        assert!((node.speed - -0.053).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_from_abbreviation() {
+        assert_eq!(PointId::from_abbreviation("SU"), Some(PointId::Body(Body::Sun)));
+        assert_eq!(PointId::from_abbreviation("kr"), Some(PointId::Body(Body::Kronos)));
+        assert_eq!(PointId::from_abbreviation("MC"), Some(PointId::Midheaven));
+        assert_eq!(PointId::from_abbreviation("XX"), None);
+    }
+
+    #[test]
+    fn test_houses() {
+        let chart = sample_chart();
+        let meridian = chart.houses(HouseSystem::Meridian).unwrap();
+        let mc = chart.point(PointId::Midheaven).unwrap().longitude;
+        assert!((meridian.cusp(10) - mc).abs() < 1e-9);
+
+        let sun = chart.point(PointId::Body(Body::Sun)).unwrap().longitude;
+        assert_eq!(chart.houses(HouseSystem::Sun).unwrap().cusp(1), sun);
+    }
+
+    #[test]
+    fn test_no_ascendant_at_pole() {
+        let datetime = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 1, 1, 0, 0, 0)
+            .unwrap();
+        let chart = Chart::new("Pole", BirthData::new(datetime, 90.0, 0.0).unwrap());
+        assert!(chart.point(PointId::Ascendant).is_none());
+        assert!(chart.point(PointId::Midheaven).is_some());
+        assert!(matches!(
+            chart.houses(HouseSystem::Ascendant),
+            Err(HouseError::AscendantUndefined { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_location() {
+        let datetime = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 1, 1, 0, 0, 0)
+            .unwrap();
+        assert!(matches!(
+            BirthData::new(datetime, 95.0, 0.0),
+            Err(ChartError::InvalidLocation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let chart = sample_chart();
+        let json = chart.to_json().unwrap();
+        assert!(json.contains("2000-01-01T13:00:00+01:00"));
+        assert_eq!(Chart::from_json(&json).unwrap(), chart);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let chart = sample_chart();
+        let path = std::env::temp_dir().join(format!("uranian-chart-{}.json", std::process::id()));
+        chart.save(&path).unwrap();
+        let loaded = Chart::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, chart);
+    }
+
+    #[test]
+    fn test_load_errors() {
+        assert!(matches!(Chart::from_json("{"), Err(ChartError::Format(_))));
+        assert!(matches!(
+            Chart::load("/nonexistent/chart.json"),
+            Err(ChartError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_invalid_location() {
+        let mut json: serde_json::Value = serde_json::from_str(&sample_chart().to_json().unwrap()).unwrap();
+        json["birth"]["latitude"] = 200.0.into();
+        let path = std::env::temp_dir().join(format!("uranian-invalid-{}.json", std::process::id()));
+        fs::write(&path, json.to_string()).unwrap();
+        let loaded = Chart::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        match loaded {
+            Err(ChartError::Format(err)) => assert!(err.to_string().contains("invalid location 200")),
+            other => panic!("expected a format error, got {:?}", other),
+        }
+    }
+}
@@ -28,4 +28,7 @@ pub const MINOR_ASPECTS: [(f32, &str); 5] = [
 ];
 
 /// Default orb for aspects in degrees
-pub const DEFAULT_ORB: f32 = 2.0;
\ No newline at end of file
+pub const DEFAULT_ORB: f32 = 2.0;
+
+/// Default orb for midpoint trees in degrees
+pub const DEFAULT_MIDPOINT_ORB: f32 = 1.5;
@@ -0,0 +1,311 @@
+//! Geocentric positions of the Sun, Moon and planets from bundled analytical
+//! theories, so that charts can be calculated without any external data.
+//!
+//! - Sun and planets Mercury to Neptune: truncated VSOP87D series
+//! - Moon: main terms of ELP-2000/82
+//! - Pluto: periodic series fitted to DE200, valid 1885-2099
+//! - Transneptunian points: Keplerian orbits, see [`crate::astrology::transneptunians`]
+//!
+//! All functions take a Julian Ephemeris Day (Terrestrial Time) and return
+//! apparent positions referred to the true equinox of date, i.e. tropical
+//! longitudes as used in the chart.
+
+mod moon;
+mod nutation;
+mod pluto;
+mod precession;
+mod vsop87;
+
+pub use nutation::{mean_obliquity, nutation, true_obliquity, Nutation};
+
+use std::ops::Range;
+
+use crate::astrology::transneptunians::{self, J1900};
+use crate::astrology::{Body, DegreePosition};
+
+/// Julian Ephemeris Day of the standard epoch J2000.0
+pub const J2000: f64 = 2451545.0;
+
+/// Julian Ephemeris Days covered by the Pluto series, 1885 January 1 to
+/// 2100 January 1. Outside this range Pluto is extrapolated and unreliable.
+pub const PLUTO_RANGE: Range<f64> = 2409542.5..2488069.5;
+
+/// Kilometres per astronomical unit
+const AU_KM: f64 = 149_597_870.7;
+
+/// Light travel time in days for one astronomical unit
+const LIGHT_TIME_PER_AU: f64 = 0.005_775_518_3;
+
+/// Half-width in days of the interval used to derive daily motion
+const SPEED_STEP: f64 = 0.5;
+
+/// Apparent geocentric ecliptic coordinates of a body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EclipticPosition {
+    /// Longitude in degrees [0, 360)
+    pub longitude: f64,
+    /// Latitude in degrees
+    pub latitude: f64,
+    /// Distance from the Earth in AU
+    pub distance: f64,
+    /// Daily motion in longitude, degrees per day (negative when retrograde)
+    pub speed: f64,
+}
+
+impl EclipticPosition {
+    /// Returns the longitude as a dial position
+    pub fn position(&self) -> DegreePosition {
+        DegreePosition::from_degrees(self.longitude)
+    }
+
+    /// Whether the body is moving backwards through the zodiac
+    pub fn is_retrograde(&self) -> bool {
+        self.speed < 0.0
+    }
+}
+
+/// Calculates the apparent geocentric position of a body for a Julian Ephemeris Day
+///
+/// Pluto is only valid within [`PLUTO_RANGE`].
+pub fn calculate(body: Body, jde: f64) -> EclipticPosition {
+    let (longitude, latitude, distance) = apparent(body, jde);
+    let before = apparent(body, jde - SPEED_STEP).0;
+    let after = apparent(body, jde + SPEED_STEP).0;
+    let motion = (after - before + 180.0).rem_euclid(360.0) - 180.0;
+
+    EclipticPosition {
+        longitude,
+        latitude,
+        distance,
+        speed: motion / (2.0 * SPEED_STEP),
+    }
+}
+
+/// Apparent geocentric longitude of a body in degrees, without the daily
+/// motion, for callers that sample many moments
+pub fn longitude(body: Body, jde: f64) -> f64 {
+    apparent(body, jde).0
+}
+
+/// Apparent longitude of the Moon's mean ascending node in degrees
+pub fn mean_node(jde: f64) -> f64 {
+    (moon::mean_node(jde) + nutation(jde).longitude).rem_euclid(360.0)
+}
+
+/// Apparent longitude and latitude in degrees and distance in AU
+fn apparent(body: Body, jde: f64) -> (f64, f64, f64) {
+    let t = (jde - J2000) / 36525.0;
+    let nutation = nutation(jde);
+
+    match body {
+        Body::Sun => {
+            let earth = vsop87::EARTH.heliocentric(jde);
+            let longitude = earth.longitude.to_degrees() + 180.0;
+            let latitude = -earth.latitude.to_degrees();
+            let (longitude, latitude) = to_fk5(longitude, latitude, t);
+            let aberration = -20.4898 / 3600.0 / earth.radius;
+            (
+                (longitude + aberration + nutation.longitude).rem_euclid(360.0),
+                latitude,
+                earth.radius,
+            )
+        }
+        Body::Moon => {
+            let moon = moon::geocentric(jde);
+            (
+                (moon.longitude + nutation.longitude).rem_euclid(360.0),
+                moon.latitude,
+                moon.distance / AU_KM,
+            )
+        }
+        _ => {
+            let (longitude, latitude, distance) = geometric_planet(body, jde);
+            let (longitude, latitude) = if body == Body::Pluto || body.is_transneptunian() {
+                (longitude, latitude)
+            } else {
+                to_fk5(longitude, latitude, t)
+            };
+            let (d_lon, d_lat) = aberration(longitude, latitude, jde);
+            (
+                (longitude + d_lon + nutation.longitude).rem_euclid(360.0),
+                latitude + d_lat,
+                distance,
+            )
+        }
+    }
+}
+
+/// Heliocentric longitude and latitude in degrees and radius in AU, mean equinox of date
+fn heliocentric(body: Body, jde: f64) -> (f64, f64, f64) {
+    let series = match body {
+        Body::Mercury => &vsop87::MERCURY,
+        Body::Venus => &vsop87::VENUS,
+        Body::Mars => &vsop87::MARS,
+        Body::Jupiter => &vsop87::JUPITER,
+        Body::Saturn => &vsop87::SATURN,
+        Body::Uranus => &vsop87::URANUS,
+        Body::Neptune => &vsop87::NEPTUNE,
+        Body::Pluto => {
+            let pluto = pluto::heliocentric(jde);
+            let (longitude, latitude) =
+                precession::precess_ecliptic(pluto.longitude, pluto.latitude, J2000, jde);
+            return (longitude, latitude, pluto.radius);
+        }
+        Body::Sun | Body::Moon => unreachable!("{} has no heliocentric orbit", body.name()),
+        _ => {
+            let point = transneptunians::heliocentric(body, jde)
+                .expect("transneptunian points have orbital elements");
+            let (longitude, latitude) =
+                precession::precess_ecliptic(point.longitude, point.latitude, J1900, jde);
+            return (longitude, latitude, point.radius);
+        }
+    };
+    let position = series.heliocentric(jde);
+    (
+        position.longitude.to_degrees(),
+        position.latitude.to_degrees(),
+        position.radius,
+    )
+}
+
+/// Geometric geocentric position of a planet, corrected for light-time
+fn geometric_planet(body: Body, jde: f64) -> (f64, f64, f64) {
+    let earth = vsop87::EARTH.heliocentric(jde);
+    let earth = rectangular(
+        earth.longitude.to_degrees(),
+        earth.latitude.to_degrees(),
+        earth.radius,
+    );
+
+    let mut light_time = 0.0;
+    let mut geocentric = [0.0; 3];
+    for _ in 0..3 {
+        let (l, b, r) = heliocentric(body, jde - light_time);
+        let planet = rectangular(l, b, r);
+        for i in 0..3 {
+            geocentric[i] = planet[i] - earth[i];
+        }
+        let distance = geocentric.iter().map(|c| c * c).sum::<f64>().sqrt();
+        light_time = LIGHT_TIME_PER_AU * distance;
+    }
+
+    let [x, y, z] = geocentric;
+    let distance = (x * x + y * y + z * z).sqrt();
+    (
+        y.atan2(x).to_degrees().rem_euclid(360.0),
+        z.atan2(x.hypot(y)).to_degrees(),
+        distance,
+    )
+}
+
+fn rectangular(longitude: f64, latitude: f64, radius: f64) -> [f64; 3] {
+    let (sin_l, cos_l) = longitude.to_radians().sin_cos();
+    let (sin_b, cos_b) = latitude.to_radians().sin_cos();
+    [radius * cos_b * cos_l, radius * cos_b * sin_l, radius * sin_b]
+}
+
+/// Converts VSOP87 coordinates to the FK5 reference frame
+fn to_fk5(longitude: f64, latitude: f64, t: f64) -> (f64, f64) {
+    let shifted = (longitude - 1.397 * t - 0.00031 * t * t).to_radians();
+    let (sin_s, cos_s) = shifted.sin_cos();
+    let d_lon = -0.09033 + 0.03916 * (cos_s + sin_s) * latitude.to_radians().tan();
+    let d_lat = 0.03916 * (cos_s - sin_s);
+    (longitude + d_lon / 3600.0, latitude + d_lat / 3600.0)
+}
+
+/// Annual aberration in longitude and latitude, degrees
+fn aberration(longitude: f64, latitude: f64, jde: f64) -> (f64, f64) {
+    const KAPPA: f64 = 20.49552 / 3600.0;
+    let t = (jde - J2000) / 36525.0;
+    let sun = vsop87::EARTH.heliocentric(jde).longitude.to_degrees() + 180.0;
+    let e = 0.016708634 - 0.000042037 * t - 0.0000001267 * t * t;
+    let perihelion = 102.93735 + 1.71946 * t + 0.00046 * t * t;
+
+    let sun_diff = (sun - longitude).to_radians();
+    let perihelion_diff = (perihelion - longitude).to_radians();
+    let lat = latitude.to_radians();
+
+    let d_lon = (-KAPPA * sun_diff.cos() + e * KAPPA * perihelion_diff.cos()) / lat.cos();
+    let d_lat = -KAPPA * lat.sin() * (sun_diff.sin() - e * perihelion_diff.sin());
+    (d_lon, d_lat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arcseconds(a: f64, b: f64) -> f64 {
+        ((a - b + 180.0).rem_euclid(360.0) - 180.0).abs() * 3600.0
+    }
+
+    #[test]
+    fn test_apparent_sun() {
+        // Meeus, example 25.b (1992 Oct 13.0 TD): 199°54'21.818"
+        let sun = calculate(Body::Sun, 2448908.5);
+        let expected = 199.0 + 54.0 / 60.0 + 21.818 / 3600.0;
+        assert!(arcseconds(sun.longitude, expected) < 0.1);
+        assert!((sun.distance - 0.99760775).abs() < 1e-7);
+        assert!((sun.speed - 0.9856).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_apparent_moon() {
+        // Meeus, example 47.a (1992 Apr 12.0 TD)
+        let moon = calculate(Body::Moon, 2448724.5);
+        assert!(arcseconds(moon.longitude, 133.167265) < 0.5);
+        assert!(arcseconds(moon.latitude, -3.229126) < 0.5);
+        assert!((moon.distance * AU_KM - 368409.7).abs() < 1.0);
+        assert!(moon.speed > 11.0 && moon.speed < 16.0);
+    }
+
+    #[test]
+    fn test_geometric_venus() {
+        // Meeus, example 33.a (1992 Dec 20.0 TD), corrected for light-time only
+        let (longitude, latitude, distance) = geometric_planet(Body::Venus, 2448976.5);
+        assert!(arcseconds(longitude, 313.08102) < 1.0);
+        assert!(arcseconds(latitude, -2.08474) < 1.0);
+        assert!((distance - 0.910947).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_apparent_venus() {
+        // Meeus, example 33.a: α 21h04m41.454s, δ -18°53'16.84" on the ecliptic of date
+        let venus = calculate(Body::Venus, 2448976.5);
+        assert!(arcseconds(venus.longitude, 313.08134) < 1.0);
+        assert!(arcseconds(venus.latitude, -2.08482) < 1.0);
+    }
+
+    #[test]
+    fn test_longitude_alone() {
+        for body in Body::ALL {
+            assert_eq!(longitude(body, J2000), calculate(body, J2000).longitude, "{:?}", body);
+        }
+    }
+
+    #[test]
+    fn test_retrograde_motion() {
+        // Mercury was retrograde from 2000 Feb 21 to Mar 14
+        let retrograde = calculate(Body::Mercury, 2451606.5);
+        assert!(retrograde.is_retrograde());
+        let direct = calculate(Body::Mercury, 2451645.5);
+        assert!(!direct.is_retrograde());
+    }
+
+    #[test]
+    fn test_all_bodies_in_range() {
+        for body in Body::ALL {
+            let position = calculate(body, J2000);
+            assert!((0.0..360.0).contains(&position.longitude), "{:?}", body);
+            assert!(position.latitude.abs() < 18.0, "{:?}", body);
+            assert!(position.distance > 0.0, "{:?}", body);
+        }
+    }
+
+    #[test]
+    fn test_position_conversion() {
+        let sun = calculate(Body::Sun, 2448908.5);
+        let position = sun.position();
+        assert_eq!(position.degrees(), 199);
+        assert_eq!(position.minutes(), 54);
+    }
+}
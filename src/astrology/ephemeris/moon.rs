@@ -0,0 +1,244 @@
+//! Geocentric position of the Moon from the main terms of ELP-2000/82, as
+//! tabulated in Meeus, Astronomical Algorithms, chapter 47.
+
+use super::J2000;
+
+/// Geocentric ecliptic coordinates of the Moon, referred to the mean equinox of date
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct LunarPosition {
+    /// Longitude in degrees [0, 360)
+    pub longitude: f64,
+    /// Latitude in degrees
+    pub latitude: f64,
+    /// Distance between the centres of the Earth and Moon in kilometres
+    pub distance: f64,
+}
+
+/// Periodic terms for longitude and distance: multipliers of D, M, M', F, then the
+/// sine coefficient in 10^-6 degrees and the cosine coefficient in metres
+const LONGITUDE_DISTANCE: [([i8; 4], f64, f64); 60] = [
+    ([0, 0, 1, 0], 6288774.0, -20905355.0),
+    ([2, 0, -1, 0], 1274027.0, -3699111.0),
+    ([2, 0, 0, 0], 658314.0, -2955968.0),
+    ([0, 0, 2, 0], 213618.0, -569925.0),
+    ([0, 1, 0, 0], -185116.0, 48888.0),
+    ([0, 0, 0, 2], -114332.0, -3149.0),
+    ([2, 0, -2, 0], 58793.0, 246158.0),
+    ([2, -1, -1, 0], 57066.0, -152138.0),
+    ([2, 0, 1, 0], 53322.0, -170733.0),
+    ([2, -1, 0, 0], 45758.0, -204586.0),
+    ([0, 1, -1, 0], -40923.0, -129620.0),
+    ([1, 0, 0, 0], -34720.0, 108743.0),
+    ([0, 1, 1, 0], -30383.0, 104755.0),
+    ([2, 0, 0, -2], 15327.0, 10321.0),
+    ([0, 0, 1, 2], -12528.0, 0.0),
+    ([0, 0, 1, -2], 10980.0, 79661.0),
+    ([4, 0, -1, 0], 10675.0, -34782.0),
+    ([0, 0, 3, 0], 10034.0, -23210.0),
+    ([4, 0, -2, 0], 8548.0, -21636.0),
+    ([2, 1, -1, 0], -7888.0, 24208.0),
+    ([2, 1, 0, 0], -6766.0, 30824.0),
+    ([1, 0, -1, 0], -5163.0, -8379.0),
+    ([1, 1, 0, 0], 4987.0, -16675.0),
+    ([2, -1, 1, 0], 4036.0, -12831.0),
+    ([2, 0, 2, 0], 3994.0, -10445.0),
+    ([4, 0, 0, 0], 3861.0, -11650.0),
+    ([2, 0, -3, 0], 3665.0, 14403.0),
+    ([0, 1, -2, 0], -2689.0, -7003.0),
+    ([2, 0, -1, 2], -2602.0, 0.0),
+    ([2, -1, -2, 0], 2390.0, 10056.0),
+    ([1, 0, 1, 0], -2348.0, 6322.0),
+    ([2, -2, 0, 0], 2236.0, -9884.0),
+    ([0, 1, 2, 0], -2120.0, 5751.0),
+    ([0, 2, 0, 0], -2069.0, 0.0),
+    ([2, -2, -1, 0], 2048.0, -4950.0),
+    ([2, 0, 1, -2], -1773.0, 4130.0),
+    ([2, 0, 0, 2], -1595.0, 0.0),
+    ([4, -1, -1, 0], 1215.0, -3958.0),
+    ([0, 0, 2, 2], -1110.0, 0.0),
+    ([3, 0, -1, 0], -892.0, 3258.0),
+    ([2, 1, 1, 0], -810.0, 2616.0),
+    ([4, -1, -2, 0], 759.0, -1897.0),
+    ([0, 2, -1, 0], -713.0, -2117.0),
+    ([2, 2, -1, 0], -700.0, 2354.0),
+    ([2, 1, -2, 0], 691.0, 0.0),
+    ([2, -1, 0, -2], 596.0, 0.0),
+    ([4, 0, 1, 0], 549.0, -1423.0),
+    ([0, 0, 4, 0], 537.0, -1117.0),
+    ([4, -1, 0, 0], 520.0, -1571.0),
+    ([1, 0, -2, 0], -487.0, -1739.0),
+    ([2, 1, 0, -2], -399.0, 0.0),
+    ([0, 0, 2, -2], -381.0, -4421.0),
+    ([1, 1, 1, 0], 351.0, 0.0),
+    ([3, 0, -2, 0], -340.0, 0.0),
+    ([4, 0, -3, 0], 330.0, 0.0),
+    ([2, -1, 2, 0], 327.0, 0.0),
+    ([0, 2, 1, 0], -323.0, 1165.0),
+    ([1, 1, -1, 0], 299.0, 0.0),
+    ([2, 0, 3, 0], 294.0, 0.0),
+    ([2, 0, -1, -2], 0.0, 8752.0),
+];
+
+/// Periodic terms for latitude: multipliers of D, M, M', F and the sine
+/// coefficient in 10^-6 degrees
+const LATITUDE: [([i8; 4], f64); 60] = [
+    ([0, 0, 0, 1], 5128122.0),
+    ([0, 0, 1, 1], 280602.0),
+    ([0, 0, 1, -1], 277693.0),
+    ([2, 0, 0, -1], 173237.0),
+    ([2, 0, -1, 1], 55413.0),
+    ([2, 0, -1, -1], 46271.0),
+    ([2, 0, 0, 1], 32573.0),
+    ([0, 0, 2, 1], 17198.0),
+    ([2, 0, 1, -1], 9266.0),
+    ([0, 0, 2, -1], 8822.0),
+    ([2, -1, 0, -1], 8216.0),
+    ([2, 0, -2, -1], 4324.0),
+    ([2, 0, 1, 1], 4200.0),
+    ([2, 1, 0, -1], -3359.0),
+    ([2, -1, -1, 1], 2463.0),
+    ([2, -1, 0, 1], 2211.0),
+    ([2, -1, -1, -1], 2065.0),
+    ([0, 1, -1, -1], -1870.0),
+    ([4, 0, -1, -1], 1828.0),
+    ([0, 1, 0, 1], -1794.0),
+    ([0, 0, 0, 3], -1749.0),
+    ([0, 1, -1, 1], -1565.0),
+    ([1, 0, 0, 1], -1491.0),
+    ([0, 1, 1, 1], -1475.0),
+    ([0, 1, 1, -1], -1410.0),
+    ([0, 1, 0, -1], -1344.0),
+    ([1, 0, 0, -1], -1335.0),
+    ([0, 0, 3, 1], 1107.0),
+    ([4, 0, 0, -1], 1021.0),
+    ([4, 0, -1, 1], 833.0),
+    ([0, 0, 1, -3], 777.0),
+    ([4, 0, -2, 1], 671.0),
+    ([2, 0, 0, -3], 607.0),
+    ([2, 0, 2, -1], 596.0),
+    ([2, -1, 1, -1], 491.0),
+    ([2, 0, -2, 1], -451.0),
+    ([0, 0, 3, -1], 439.0),
+    ([2, 0, 2, 1], 422.0),
+    ([2, 0, -3, -1], 421.0),
+    ([2, 1, -1, 1], -366.0),
+    ([2, 1, 0, 1], -351.0),
+    ([4, 0, 0, 1], 331.0),
+    ([2, -1, 1, 1], 315.0),
+    ([2, -2, 0, -1], 302.0),
+    ([0, 0, 1, 3], -283.0),
+    ([2, 1, 1, -1], -229.0),
+    ([1, 1, 0, -1], 223.0),
+    ([1, 1, 0, 1], 223.0),
+    ([0, 1, -2, -1], -220.0),
+    ([2, 1, -1, -1], -220.0),
+    ([1, 0, 1, 1], -185.0),
+    ([2, -1, -2, -1], 181.0),
+    ([0, 1, 2, 1], -177.0),
+    ([4, 0, -2, -1], 176.0),
+    ([4, -1, -1, -1], 166.0),
+    ([1, 0, 1, -1], -164.0),
+    ([4, 0, 1, -1], 132.0),
+    ([1, 0, -1, -1], -119.0),
+    ([4, -1, 0, -1], 115.0),
+    ([2, -2, 0, 1], 107.0),
+];
+
+/// Calculates the geometric position of the Moon for a Julian Ephemeris Day
+pub(super) fn geocentric(jde: f64) -> LunarPosition {
+    let t = (jde - J2000) / 36525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let t4 = t3 * t;
+
+    // Mean longitude, mean elongation, the Sun's and Moon's mean anomalies and the
+    // Moon's argument of latitude
+    let mean_longitude =
+        218.3164477 + 481267.88123421 * t - 0.0015786 * t2 + t3 / 538841.0 - t4 / 65194000.0;
+    let arguments = [
+        297.8501921 + 445267.1114034 * t - 0.0018819 * t2 + t3 / 545868.0 - t4 / 113065000.0,
+        357.5291092 + 35999.0502909 * t - 0.0001536 * t2 + t3 / 24490000.0,
+        134.9633964 + 477198.8675055 * t + 0.0087414 * t2 + t3 / 69699.0 - t4 / 14712000.0,
+        93.2720950 + 483202.0175233 * t - 0.0036539 * t2 - t3 / 3526000.0 + t4 / 863310000.0,
+    ];
+    let [_, _, anomaly, latitude_argument] = arguments;
+
+    // Planetary perturbations from Venus and Jupiter, and the flattening of the Earth
+    let a1 = (119.75 + 131.849 * t).to_radians();
+    let a2 = (53.09 + 479264.290 * t).to_radians();
+    let a3 = (313.45 + 481266.484 * t).to_radians();
+
+    // Eccentricity of the Earth's orbit scales the terms containing M
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let eccentricity = |m: i8| e.powi(m.unsigned_abs() as i32);
+    let angle = |multipliers: &[i8; 4]| -> f64 {
+        multipliers
+            .iter()
+            .zip(arguments.iter())
+            .map(|(m, a)| *m as f64 * a)
+            .sum::<f64>()
+            .to_radians()
+    };
+
+    let mut sum_l = 0.0;
+    let mut sum_r = 0.0;
+    for (multipliers, l, r) in LONGITUDE_DISTANCE.iter() {
+        let argument = angle(multipliers);
+        let factor = eccentricity(multipliers[1]);
+        sum_l += l * factor * argument.sin();
+        sum_r += r * factor * argument.cos();
+    }
+
+    let mut sum_b = 0.0;
+    for (multipliers, b) in LATITUDE.iter() {
+        sum_b += b * eccentricity(multipliers[1]) * angle(multipliers).sin();
+    }
+
+    let l_rad = mean_longitude.to_radians();
+    let f_rad = latitude_argument.to_radians();
+    let m_rad = anomaly.to_radians();
+    sum_l += 3958.0 * a1.sin() + 1962.0 * (l_rad - f_rad).sin() + 318.0 * a2.sin();
+    sum_b += -2235.0 * l_rad.sin()
+        + 382.0 * a3.sin()
+        + 175.0 * (a1 - f_rad).sin()
+        + 175.0 * (a1 + f_rad).sin()
+        + 127.0 * (l_rad - m_rad).sin()
+        - 115.0 * (l_rad + m_rad).sin();
+
+    LunarPosition {
+        longitude: (mean_longitude + sum_l / 1e6).rem_euclid(360.0),
+        latitude: sum_b / 1e6,
+        distance: 385000.56 + sum_r / 1000.0,
+    }
+}
+
+/// Longitude of the mean ascending node of the lunar orbit in degrees,
+/// referred to the mean equinox of date (Meeus 47.7)
+pub(super) fn mean_node(jde: f64) -> f64 {
+    let t = (jde - J2000) / 36525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let node = 125.0445479 - 1934.1362891 * t + 0.0020754 * t2 + t3 / 467441.0
+        - t3 * t / 60616000.0;
+    node.rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lunar_position() {
+        // Meeus, example 47.a (1992 Apr 12.0 TD)
+        let moon = geocentric(2448724.5);
+        assert!((moon.longitude - 133.162655).abs() < 1e-6);
+        assert!((moon.latitude - (-3.229126)).abs() < 1e-6);
+        assert!((moon.distance - 368409.7).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_mean_node() {
+        // Meeus, example 47.a: Ω = 274.400656°
+        assert!((mean_node(2448724.5) - 274.400656).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,150 @@
+//! Nutation in longitude and obliquity (IAU 1980 theory) and the obliquity
+//! of the ecliptic.
+
+use super::J2000;
+
+/// Nutation components, both in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Nutation {
+    /// Nutation in longitude (Δψ)
+    pub longitude: f64,
+    /// Nutation in obliquity (Δε)
+    pub obliquity: f64,
+}
+
+/// Periodic terms: multipliers of D, M, M', F, Ω, then Δψ sine coefficient and its
+/// rate, and Δε cosine coefficient and its rate, in units of 0.0001".
+const TERMS: [([i8; 5], f64, f64, f64, f64); 63] = [
+    ([0, 0, 0, 0, 1], -171996.0, -174.2, 92025.0, 8.9),
+    ([-2, 0, 0, 2, 2], -13187.0, -1.6, 5736.0, -3.1),
+    ([0, 0, 0, 2, 2], -2274.0, -0.2, 977.0, -0.5),
+    ([0, 0, 0, 0, 2], 2062.0, 0.2, -895.0, 0.5),
+    ([0, 1, 0, 0, 0], 1426.0, -3.4, 54.0, -0.1),
+    ([0, 0, 1, 0, 0], 712.0, 0.1, -7.0, 0.0),
+    ([-2, 1, 0, 2, 2], -517.0, 1.2, 224.0, -0.6),
+    ([0, 0, 0, 2, 1], -386.0, -0.4, 200.0, 0.0),
+    ([0, 0, 1, 2, 2], -301.0, 0.0, 129.0, -0.1),
+    ([-2, -1, 0, 2, 2], 217.0, -0.5, -95.0, 0.3),
+    ([-2, 0, 1, 0, 0], -158.0, 0.0, 0.0, 0.0),
+    ([-2, 0, 0, 2, 1], 129.0, 0.1, -70.0, 0.0),
+    ([0, 0, -1, 2, 2], 123.0, 0.0, -53.0, 0.0),
+    ([2, 0, 0, 0, 0], 63.0, 0.0, 0.0, 0.0),
+    ([0, 0, 1, 0, 1], 63.0, 0.1, -33.0, 0.0),
+    ([2, 0, -1, 2, 2], -59.0, 0.0, 26.0, 0.0),
+    ([0, 0, -1, 0, 1], -58.0, -0.1, 32.0, 0.0),
+    ([0, 0, 1, 2, 1], -51.0, 0.0, 27.0, 0.0),
+    ([-2, 0, 2, 0, 0], 48.0, 0.0, 0.0, 0.0),
+    ([0, 0, -2, 2, 1], 46.0, 0.0, -24.0, 0.0),
+    ([2, 0, 0, 2, 2], -38.0, 0.0, 16.0, 0.0),
+    ([0, 0, 2, 2, 2], -31.0, 0.0, 13.0, 0.0),
+    ([0, 0, 2, 0, 0], 29.0, 0.0, 0.0, 0.0),
+    ([-2, 0, 1, 2, 2], 29.0, 0.0, -12.0, 0.0),
+    ([0, 0, 0, 2, 0], 26.0, 0.0, 0.0, 0.0),
+    ([-2, 0, 0, 2, 0], -22.0, 0.0, 0.0, 0.0),
+    ([0, 0, -1, 2, 1], 21.0, 0.0, -10.0, 0.0),
+    ([0, 2, 0, 0, 0], 17.0, -0.1, 0.0, 0.0),
+    ([2, 0, -1, 0, 1], 16.0, 0.0, -8.0, 0.0),
+    ([-2, 2, 0, 2, 2], -16.0, 0.1, 7.0, 0.0),
+    ([0, 1, 0, 0, 1], -15.0, 0.0, 9.0, 0.0),
+    ([-2, 0, 1, 0, 1], -13.0, 0.0, 7.0, 0.0),
+    ([0, -1, 0, 0, 1], -12.0, 0.0, 6.0, 0.0),
+    ([0, 0, 2, -2, 0], 11.0, 0.0, 0.0, 0.0),
+    ([2, 0, -1, 2, 1], -10.0, 0.0, 5.0, 0.0),
+    ([2, 0, 1, 2, 2], -8.0, 0.0, 3.0, 0.0),
+    ([0, 1, 0, 2, 2], 7.0, 0.0, -3.0, 0.0),
+    ([-2, 1, 1, 0, 0], -7.0, 0.0, 0.0, 0.0),
+    ([0, -1, 0, 2, 2], -7.0, 0.0, 3.0, 0.0),
+    ([2, 0, 0, 2, 1], -7.0, 0.0, 3.0, 0.0),
+    ([2, 0, 1, 0, 0], 6.0, 0.0, 0.0, 0.0),
+    ([-2, 0, 2, 2, 2], 6.0, 0.0, -3.0, 0.0),
+    ([-2, 0, 1, 2, 1], 6.0, 0.0, -3.0, 0.0),
+    ([2, 0, -2, 0, 1], -6.0, 0.0, 3.0, 0.0),
+    ([2, 0, 0, 0, 1], -6.0, 0.0, 3.0, 0.0),
+    ([0, -1, 1, 0, 0], 5.0, 0.0, 0.0, 0.0),
+    ([-2, -1, 0, 2, 1], -5.0, 0.0, 3.0, 0.0),
+    ([-2, 0, 0, 0, 1], -5.0, 0.0, 3.0, 0.0),
+    ([0, 0, 2, 2, 1], -5.0, 0.0, 3.0, 0.0),
+    ([-2, 0, 2, 0, 1], 4.0, 0.0, 0.0, 0.0),
+    ([-2, 1, 0, 2, 1], 4.0, 0.0, 0.0, 0.0),
+    ([0, 0, 1, -2, 0], 4.0, 0.0, 0.0, 0.0),
+    ([-1, 0, 1, 0, 0], -4.0, 0.0, 0.0, 0.0),
+    ([-2, 1, 0, 0, 0], -4.0, 0.0, 0.0, 0.0),
+    ([1, 0, 0, 0, 0], -4.0, 0.0, 0.0, 0.0),
+    ([0, 0, 1, 2, 0], 3.0, 0.0, 0.0, 0.0),
+    ([0, 0, -2, 2, 2], -3.0, 0.0, 0.0, 0.0),
+    ([-1, -1, 1, 0, 0], -3.0, 0.0, 0.0, 0.0),
+    ([0, 1, 1, 0, 0], -3.0, 0.0, 0.0, 0.0),
+    ([0, -1, 1, 2, 2], -3.0, 0.0, 0.0, 0.0),
+    ([2, -1, -1, 2, 2], -3.0, 0.0, 0.0, 0.0),
+    ([0, 0, 3, 2, 2], -3.0, 0.0, 0.0, 0.0),
+    ([2, -1, 0, 2, 2], -3.0, 0.0, 0.0, 0.0),
+];
+
+/// Calculates the nutation for a Julian Ephemeris Day
+pub fn nutation(jde: f64) -> Nutation {
+    let t = (jde - J2000) / 36525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    // Fundamental arguments: mean elongation of the Moon, mean anomalies of the
+    // Sun and Moon, Moon's argument of latitude and longitude of its node
+    let arguments = [
+        297.85036 + 445267.111480 * t - 0.0019142 * t2 + t3 / 189474.0,
+        357.52772 + 35999.050340 * t - 0.0001603 * t2 - t3 / 300000.0,
+        134.96298 + 477198.867398 * t + 0.0086972 * t2 + t3 / 56250.0,
+        93.27191 + 483202.017538 * t - 0.0036825 * t2 + t3 / 327270.0,
+        125.04452 - 1934.136261 * t + 0.0020708 * t2 + t3 / 450000.0,
+    ];
+
+    let mut longitude = 0.0;
+    let mut obliquity = 0.0;
+    for (multipliers, psi, psi_rate, eps, eps_rate) in TERMS.iter() {
+        let argument: f64 = multipliers
+            .iter()
+            .zip(arguments.iter())
+            .map(|(m, a)| *m as f64 * a)
+            .sum::<f64>()
+            .to_radians();
+        longitude += (psi + psi_rate * t) * argument.sin();
+        obliquity += (eps + eps_rate * t) * argument.cos();
+    }
+
+    Nutation {
+        longitude: longitude / 36_000_000.0,
+        obliquity: obliquity / 36_000_000.0,
+    }
+}
+
+/// Mean obliquity of the ecliptic in degrees (IAU 1980)
+pub fn mean_obliquity(jde: f64) -> f64 {
+    let t = (jde - J2000) / 36525.0;
+    let seconds = 21.448 - 46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t;
+    23.0 + 26.0 / 60.0 + seconds / 3600.0
+}
+
+/// True obliquity of the ecliptic in degrees, including nutation
+pub fn true_obliquity(jde: f64) -> f64 {
+    mean_obliquity(jde) + nutation(jde).obliquity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nutation() {
+        // Meeus, example 22.a (1987 Apr 10.0 TD)
+        let n = nutation(2446895.5);
+        assert!((n.longitude * 3600.0 - (-3.788)).abs() < 0.01);
+        assert!((n.obliquity * 3600.0 - 9.443).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_obliquity() {
+        let jde = 2446895.5;
+        let mean = 23.0 + 26.0 / 60.0 + 27.407 / 3600.0;
+        let true_value = 23.0 + 26.0 / 60.0 + 36.850 / 3600.0;
+        assert!((mean_obliquity(jde) - mean).abs() * 3600.0 < 0.01);
+        assert!((true_obliquity(jde) - true_value).abs() * 3600.0 < 0.01);
+    }
+}
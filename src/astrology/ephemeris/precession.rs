@@ -0,0 +1,56 @@
+//! Precession of ecliptic coordinates between two equinoxes.
+
+use super::J2000;
+
+/// Precesses ecliptic longitude and latitude (degrees) from the equinox of one
+/// Julian Ephemeris Day to another (Meeus, Astronomical Algorithms, 21.5)
+pub(crate) fn precess_ecliptic(longitude: f64, latitude: f64, from_jde: f64, to_jde: f64) -> (f64, f64) {
+    let big_t = (from_jde - J2000) / 36525.0;
+    let t = (to_jde - from_jde) / 36525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let eta = ((47.0029 - 0.06603 * big_t + 0.000598 * big_t * big_t) * t
+        + (-0.03302 + 0.000598 * big_t) * t2
+        + 0.000060 * t3)
+        / 3600.0;
+    let pi = 174.876384
+        + ((3289.4789 * big_t + 0.60622 * big_t * big_t) - (869.8089 + 0.50491 * big_t) * t
+            + 0.03536 * t2)
+            / 3600.0;
+    let p = ((5029.0966 + 2.22226 * big_t - 0.000042 * big_t * big_t) * t
+        + (1.11113 - 0.000042 * big_t) * t2
+        - 0.000006 * t3)
+        / 3600.0;
+
+    let (sin_eta, cos_eta) = eta.to_radians().sin_cos();
+    let (sin_lat, cos_lat) = latitude.to_radians().sin_cos();
+    let (sin_diff, cos_diff) = (pi - longitude).to_radians().sin_cos();
+
+    let a = cos_eta * cos_lat * sin_diff - sin_eta * sin_lat;
+    let b = cos_lat * cos_diff;
+    let c = cos_eta * sin_lat + sin_eta * cos_lat * sin_diff;
+
+    let precessed_longitude = (p + pi - a.atan2(b).to_degrees()).rem_euclid(360.0);
+    (precessed_longitude, c.asin().to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity() {
+        let (lon, lat) = precess_ecliptic(149.48194, 1.76549, J2000, J2000);
+        assert!((lon - 149.48194).abs() < 1e-9);
+        assert!((lat - 1.76549).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_precession_rate() {
+        // General precession in longitude is about 50.3" per year
+        let (lon, lat) = precess_ecliptic(10.0, 0.0, J2000, J2000 + 36525.0);
+        assert!((lon - 10.0 - 1.397).abs() < 0.002);
+        assert!(lat.abs() < 0.02);
+    }
+}
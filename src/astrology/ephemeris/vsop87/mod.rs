@@ -0,0 +1,111 @@
+//! Evaluation of VSOP87 planetary series.
+//!
+//! Each coordinate is a polynomial in Julian millennia whose coefficients are
+//! sums of periodic terms `A cos(B + C t)`, with amplitudes stored in units of
+//! 10^-8 radians (or AU for the radius vector).
+
+mod earth;
+mod jupiter;
+mod mars;
+mod mercury;
+mod neptune;
+mod saturn;
+mod uranus;
+mod venus;
+
+pub(super) use earth::EARTH;
+pub(super) use jupiter::JUPITER;
+pub(super) use mars::MARS;
+pub(super) use mercury::MERCURY;
+pub(super) use neptune::NEPTUNE;
+pub(super) use saturn::SATURN;
+pub(super) use uranus::URANUS;
+pub(super) use venus::VENUS;
+
+use std::f64::consts::TAU;
+
+/// The periodic series of one planet, grouped by power of time
+pub(super) struct Series {
+    pub longitude: &'static [&'static [[f64; 3]]],
+    pub latitude: &'static [&'static [[f64; 3]]],
+    pub radius: &'static [&'static [[f64; 3]]],
+}
+
+/// Heliocentric ecliptic coordinates of a planet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Heliocentric {
+    /// Longitude in radians [0, 2π)
+    pub longitude: f64,
+    /// Latitude in radians
+    pub latitude: f64,
+    /// Radius vector in AU
+    pub radius: f64,
+}
+
+impl Series {
+    /// Evaluates the series for a Julian Ephemeris Day
+    pub fn heliocentric(&self, jde: f64) -> Heliocentric {
+        let t = (jde - super::J2000) / 365_250.0;
+        Heliocentric {
+            longitude: evaluate(self.longitude, t).rem_euclid(TAU),
+            latitude: evaluate(self.latitude, t),
+            radius: evaluate(self.radius, t),
+        }
+    }
+}
+
+fn evaluate(groups: &[&[[f64; 3]]], t: f64) -> f64 {
+    let mut power = 1.0;
+    let mut total = 0.0;
+    for terms in groups {
+        let sum: f64 = terms.iter().map(|[a, b, c]| a * (b + c * t).cos()).sum();
+        total += sum * power;
+        power *= t;
+    }
+    total * 1e-8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_earth_heliocentric() {
+        // Meeus, Astronomical Algorithms, example 25.b (1992 Oct 13.0 TD)
+        let earth = EARTH.heliocentric(2448908.5);
+        assert!((earth.longitude.to_degrees() - 19.907372).abs() < 1e-6);
+        assert!((earth.latitude.to_degrees() * 3600.0 - (-0.644)).abs() < 0.01);
+        assert!((earth.radius - 0.99760775).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_venus_heliocentric() {
+        // Meeus, example 32.a (1992 Dec 20.0 TD)
+        let venus = VENUS.heliocentric(2448976.5);
+        assert!((venus.longitude.to_degrees() - 26.11428).abs() < 1e-5);
+        assert!((venus.latitude.to_degrees() - (-2.62070)).abs() < 1e-5);
+        assert!((venus.radius - 0.724603).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_planets_at_j2000() {
+        // VSOP87D check values (Bretagnon & Francou, vsop87.chk) for
+        // JD 2451545.0 in radians; the truncated series agree within 1"
+        let expected = [
+            ("Mercury", &MERCURY, 4.4293481036, -0.0527573409),
+            ("Venus", &VENUS, 3.1870221833, 0.0569782849),
+            ("Earth", &EARTH, 1.7519238681, -0.0000039656),
+            ("Mars", &MARS, 6.2735389983, -0.0247779824),
+            ("Jupiter", &JUPITER, 0.6334614186, -0.0205001039),
+            ("Saturn", &SATURN, 0.7980038761, -0.0401984149),
+            ("Uranus", &URANUS, 5.5225485803, -0.0119527838),
+            ("Neptune", &NEPTUNE, 5.3045629252, 0.0042236789),
+        ];
+        let arcseconds = |a: f64, b: f64| (a - b).to_degrees().abs() * 3600.0;
+        for (name, series, longitude, latitude) in expected {
+            let position = series.heliocentric(super::super::J2000);
+            assert!(arcseconds(position.longitude, longitude) < 1.0, "{}", name);
+            assert!(arcseconds(position.latitude, latitude) < 1.0, "{}", name);
+        }
+    }
+}
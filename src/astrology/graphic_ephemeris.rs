@@ -0,0 +1,228 @@
+//! The graphic ephemeris: body longitudes reduced to a dial's span and
+//! followed over time, with the natal points as fixed levels.
+//!
+//! On the 45° or 90° sweep a transit shows where a body's trace crosses a
+//! natal level, whatever the aspect on the hard axis.
+
+use crate::astrology::chart::{Chart, PointId};
+use crate::astrology::midpoints::reduce;
+use crate::astrology::transits::{max_speed, SearchProgress};
+use crate::astrology::{ephemeris, time, Body, Harmonic};
+
+/// Most samples taken along each trace, however long the period
+const MAX_SAMPLES: f64 = 4000.0;
+
+/// Shortest step in days between samples
+const MIN_STEP: f64 = 1.0 / 24.0;
+
+/// How many times wider than a view the sampled period may be before traces
+/// held back by [`MAX_SAMPLES`] no longer cover the view in full detail
+const DETAIL_RATIO: f64 = 3.0;
+
+/// A body's reduced longitude over time, broken where it wraps round the span
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    pub body: Body,
+    /// Runs of (Julian Day, position on the dial) without a wrap
+    pub segments: Vec<Vec<(f64, f64)>>,
+}
+
+/// Traces of moving bodies over a period on one harmonic dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphicEphemeris {
+    pub harmonic: Harmonic,
+    /// Julian Days in Universal Time of the period
+    pub start: f64,
+    pub end: f64,
+    pub traces: Vec<Trace>,
+    /// Bodies left out because they move half the span or more between
+    /// samples, where their traces would only be noise. A shorter period
+    /// follows them.
+    pub skipped: Vec<Body>,
+    /// Natal points reduced to the dial, drawn as horizontal levels
+    pub natal: Vec<(PointId, f64)>,
+    /// Whether the sample limit made the steps longer than some body needs
+    coarse: bool,
+}
+
+impl GraphicEphemeris {
+    /// Samples each body over the period, often enough to follow it on the span,
+    /// but at most [`MAX_SAMPLES`] times
+    pub fn new(bodies: &[Body], natal: Option<&Chart>, harmonic: Harmonic, start: f64, end: f64) -> Self {
+        Self::with_progress(bodies, natal, harmonic, start, end, &SearchProgress::default())
+    }
+
+    /// Like [`Self::new`], reporting progress as it goes. Once cancelled it
+    /// stops early, leaving the traces unfinished.
+    pub fn with_progress(
+        bodies: &[Body],
+        natal: Option<&Chart>,
+        harmonic: Harmonic,
+        start: f64,
+        end: f64,
+        progress: &SearchProgress,
+    ) -> Self {
+        let span = span(harmonic);
+        let mut coarse = false;
+        let mut skipped = Vec::new();
+        let mut steps: Vec<(Body, f64, usize)> = Vec::with_capacity(bodies.len());
+        for &body in bodies {
+            let needed = (span / 8.0 / max_speed(body)).max(MIN_STEP);
+            let step = needed.max((end - start) / MAX_SAMPLES);
+            coarse |= step > needed;
+            if step * max_speed(body) >= span / 2.0 {
+                skipped.push(body);
+            } else {
+                steps.push((body, step, ((end - start) / step).ceil().max(1.0) as usize));
+            }
+        }
+        progress.begin(steps.iter().map(|(_, _, count)| count + 1).sum());
+
+        let mut traces = Vec::with_capacity(steps.len());
+        for (body, step, count) in steps {
+            let mut samples: Vec<(f64, f64)> = Vec::with_capacity(count + 1);
+            for i in 0..=count {
+                if progress.is_cancelled() {
+                    break;
+                }
+                let jd = (start + i as f64 * step).min(end);
+                let longitude = ephemeris::longitude(body, time::julian_ephemeris_day(jd));
+                samples.push((jd, reduce(longitude, harmonic)));
+                progress.advance();
+            }
+            traces.push(Trace {
+                body,
+                segments: split_at_wraps(samples.into_iter(), span),
+            });
+        }
+
+        let natal = natal
+            .map(|chart| {
+                chart
+                    .points()
+                    .iter()
+                    .map(|point| (point.id, reduce(point.longitude, harmonic)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            harmonic,
+            start,
+            end,
+            traces,
+            skipped,
+            natal,
+            coarse,
+        }
+    }
+
+    /// Whether the traces follow the bodies over a view as closely as sampling
+    /// the view itself would
+    pub fn covers(&self, start: f64, end: f64) -> bool {
+        let within = self.start <= start && end <= self.end;
+        within && (!self.coarse || self.end - self.start <= (end - start) * DETAIL_RATIO)
+    }
+
+    /// Degrees the vertical axis spans
+    pub fn span(&self) -> f64 {
+        span(self.harmonic)
+    }
+}
+
+fn span(harmonic: Harmonic) -> f64 {
+    360.0 / harmonic.to_number() as f64
+}
+
+/// Breaks a run of samples wherever it jumps across the top or bottom of the span
+fn split_at_wraps(samples: impl Iterator<Item = (f64, f64)>, span: f64) -> Vec<Vec<(f64, f64)>> {
+    let mut segments: Vec<Vec<(f64, f64)>> = Vec::new();
+    let mut current: Vec<(f64, f64)> = Vec::new();
+    for sample in samples {
+        if let Some(&last) = current.last() {
+            if (sample.1 - last.1).abs() > span / 2.0 {
+                segments.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(sample);
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::BirthData;
+    use chrono::{FixedOffset, TimeZone};
+
+    #[test]
+    fn test_traces() {
+        let start = ephemeris::J2000;
+        let graph = GraphicEphemeris::new(&[Body::Sun, Body::Moon], None, Harmonic::Eighth, start, start + 365.0);
+        assert_eq!(graph.span(), 45.0);
+        assert_eq!(graph.traces.len(), 2);
+
+        // The Sun sweeps the 45° span eight times a year, the Moon far more often
+        let sun = &graph.traces[0];
+        assert!(sun.segments.len() >= 8 && sun.segments.len() <= 9);
+        assert!(graph.traces[1].segments.len() > 90);
+        for segment in &sun.segments {
+            assert!(segment.iter().all(|&(jd, position)| {
+                (start..=start + 365.0).contains(&jd) && (0.0..45.0).contains(&position)
+            }));
+            // Within a segment the Sun only moves forwards
+            assert!(segment.windows(2).all(|pair| pair[1].1 >= pair[0].1));
+        }
+    }
+
+    #[test]
+    fn test_too_fast_to_follow() {
+        // Over twenty years the Moon would move about 28° between samples on the 45° dial
+        let start = ephemeris::J2000;
+        let end = start + 20.0 * 365.25;
+        let graph = GraphicEphemeris::new(&[Body::Sun, Body::Moon], None, Harmonic::Eighth, start, end);
+        assert_eq!(graph.skipped, [Body::Moon]);
+        assert_eq!(graph.traces.len(), 1);
+        assert_eq!(graph.traces[0].body, Body::Sun);
+
+        // The capped traces only cover views of a similar width in full detail
+        assert!(graph.covers(start, end));
+        assert!(!graph.covers(start, start + 365.0));
+        assert!(!graph.covers(start - 1.0, end));
+
+        // A month is sampled finely enough for the Moon, and so covers any view within it
+        let month = GraphicEphemeris::new(&[Body::Moon], None, Harmonic::Eighth, start, start + 30.0);
+        assert!(month.skipped.is_empty());
+        assert!(month.covers(start + 10.0, start + 11.0));
+    }
+
+    #[test]
+    fn test_progress_and_cancel() {
+        let (start, end) = (ephemeris::J2000, ephemeris::J2000 + 365.0);
+        let progress = SearchProgress::default();
+        let graph = GraphicEphemeris::with_progress(&[Body::Mars], None, Harmonic::Fourth, start, end, &progress);
+        assert_eq!(graph, GraphicEphemeris::new(&[Body::Mars], None, Harmonic::Fourth, start, end));
+        assert_eq!(progress.fraction(), 1.0);
+
+        let cancelled = SearchProgress::default();
+        cancelled.cancel();
+        let graph = GraphicEphemeris::with_progress(&[Body::Mars], None, Harmonic::Fourth, start, end, &cancelled);
+        assert!(graph.traces[0].segments.is_empty());
+        assert_eq!(cancelled.fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_natal_levels() {
+        let datetime = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 1, 1, 12, 0, 0)
+            .unwrap();
+        let chart = Chart::new("Sample", BirthData::new(datetime, 0.0, 0.0).unwrap());
+        let graph = GraphicEphemeris::new(&[], Some(&chart), Harmonic::Fourth, 0.0, 1.0);
+        assert_eq!(graph.natal.len(), chart.points().len());
+        assert!(graph.natal.iter().all(|&(_, position)| (0.0..90.0).contains(&position)));
+    }
+}
@@ -1,83 +1,108 @@
+use std::num::NonZeroU32;
+
+use crate::astrology::DegreePosition;
+
 /// Represents a harmonic dial setting
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum Harmonic {
+    #[default]
     First,   // 360° - standard zodiac
     Second,  // 180° - opposition dial
     Fourth,  // 90° - square dial
     Eighth,  // 45° - eighth harmonic dial
     Sixteenth, // 22.5° - sixteenth harmonic dial
-    Custom(u8), // Custom harmonic
+    Custom(CustomHarmonic), // Any other harmonic, e.g. 5, 7 or research harmonics
+}
+
+/// The number of a harmonic without a named variant, only made by [`Harmonic::from_number`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomHarmonic(NonZeroU32);
+
+impl CustomHarmonic {
+    /// Returns the harmonic number
+    pub fn get(&self) -> u32 {
+        self.0.get()
+    }
 }
 
 impl Harmonic {
-    /// Creates a harmonic from a number
-    pub fn from_number(num: u8) -> Self {
-        match num {
+    /// Highest harmonic number, so that four arms per span can still be counted
+    pub const MAX_NUMBER: u32 = u32::MAX / 4;
+
+    /// Creates a harmonic from a number, or `None` for 0 and numbers above [`Self::MAX_NUMBER`]
+    pub fn from_number(num: u32) -> Option<Self> {
+        let harmonic = match num {
             1 => Self::First,
             2 => Self::Second,
             4 => Self::Fourth,
             8 => Self::Eighth,
             16 => Self::Sixteenth,
-            n => Self::Custom(n),
-        }
+            n if n <= Self::MAX_NUMBER => Self::Custom(CustomHarmonic(NonZeroU32::new(n)?)),
+            _ => return None,
+        };
+        Some(harmonic)
     }
     
     /// Converts to a number
-    pub fn to_number(&self) -> u8 {
+    pub fn to_number(&self) -> u32 {
         match self {
             Self::First => 1,
             Self::Second => 2,
             Self::Fourth => 4,
             Self::Eighth => 8,
             Self::Sixteenth => 16,
-            Self::Custom(n) => *n,
+            Self::Custom(n) => n.get(),
         }
     }
     
     /// Returns the angle span for this harmonic
-    pub fn angle_span(&self) -> f32 {
-        360.0 / self.to_number() as f32
+    pub fn angle_span(&self) -> f64 {
+        360.0 / self.to_number() as f64
     }
     
-    /// Returns the number of arms to display
-    pub fn arm_count(&self) -> u8 {
-        match self {
-            Self::First => 4,     // 4 arms for cardinal points
-            Self::Second => 8,    // 8 arms
-            Self::Fourth => 16,   // 16 arms
-            Self::Eighth => 32,   // 32 arms
-            Self::Sixteenth => 64, // 64 arms
-            Self::Custom(n) => n * 4, // 4 times the harmonic number
-        }
+    /// Returns the number of arms to display: four per harmonic span
+    pub fn arm_count(&self) -> u32 {
+        self.to_number() * 4
+    }
+    
+    /// Returns the angles of the arms in degrees from the red arm, main arms included
+    pub fn arm_angles(&self) -> impl Iterator<Item = f64> {
+        let count = self.arm_count();
+        (0..count).map(move |i| i as f64 * 360.0 / count as f64)
     }
     
     /// Returns a display name for this harmonic
     pub fn display_name(&self) -> String {
         match self {
-            Self::First => format!("1st (360°)"),
-            Self::Second => format!("2nd (180°)"),
-            Self::Fourth => format!("4th (90°)"),
-            Self::Eighth => format!("8th (45°)"),
-            Self::Sixteenth => format!("16th (22.5°)"),
-            Self::Custom(n) => format!("{}th ({:.1}°)", n, 360.0 / *n as f32),
+            Self::First => "1st (360°)".to_string(),
+            Self::Second => "2nd (180°)".to_string(),
+            Self::Fourth => "4th (90°)".to_string(),
+            Self::Eighth => "8th (45°)".to_string(),
+            Self::Sixteenth => "16th (22.5°)".to_string(),
+            Self::Custom(_) => {
+                let n = self.to_number();
+                let suffix = match (n % 10, n % 100) {
+                    (_, 11..=13) => "th",
+                    (1, _) => "st",
+                    (2, _) => "nd",
+                    (3, _) => "rd",
+                    _ => "th",
+                };
+                let span = DegreePosition::from_degrees(self.angle_span()).format();
+                format!("{}{} ({})", n, suffix, span)
+            }
         }
     }
 }
 
-impl Default for Harmonic {
-    fn default() -> Self {
-        Self::First
-    }
-}
-
 /// Calculates the position in the specified harmonic
-pub fn calculate_harmonic_position(position: f32, harmonic: Harmonic) -> f32 {
-    (position * harmonic.to_number() as f32) % 360.0
+pub fn calculate_harmonic_position(position: f64, harmonic: Harmonic) -> f64 {
+    (position * harmonic.to_number() as f64).rem_euclid(360.0)
 }
 
 /// Calculates the original position from a harmonic position
-pub fn calculate_original_position(harmonic_position: f32, harmonic: Harmonic) -> f32 {
-    (harmonic_position / harmonic.to_number() as f32) % 360.0
+pub fn calculate_original_position(harmonic_position: f64, harmonic: Harmonic) -> f64 {
+    harmonic_position.rem_euclid(360.0) / harmonic.to_number() as f64
 }
 
 #[cfg(test)]
@@ -86,14 +111,14 @@ mod tests {
     
     #[test]
     fn test_harmonic_from_number() {
-        assert_eq!(Harmonic::from_number(1), Harmonic::First);
-        assert_eq!(Harmonic::from_number(2), Harmonic::Second);
-        assert_eq!(Harmonic::from_number(4), Harmonic::Fourth);
-        assert_eq!(Harmonic::from_number(8), Harmonic::Eighth);
-        assert_eq!(Harmonic::from_number(16), Harmonic::Sixteenth);
+        assert_eq!(Harmonic::from_number(1), Some(Harmonic::First));
+        assert_eq!(Harmonic::from_number(2), Some(Harmonic::Second));
+        assert_eq!(Harmonic::from_number(4), Some(Harmonic::Fourth));
+        assert_eq!(Harmonic::from_number(8), Some(Harmonic::Eighth));
+        assert_eq!(Harmonic::from_number(16), Some(Harmonic::Sixteenth));
         
-        if let Harmonic::Custom(n) = Harmonic::from_number(7) {
-            assert_eq!(n, 7);
+        if let Some(Harmonic::Custom(n)) = Harmonic::from_number(7) {
+            assert_eq!(n.get(), 7);
         } else {
             panic!("Expected Custom(7)");
         }
@@ -106,7 +131,7 @@ mod tests {
         assert_eq!(Harmonic::Fourth.to_number(), 4);
         assert_eq!(Harmonic::Eighth.to_number(), 8);
         assert_eq!(Harmonic::Sixteenth.to_number(), 16);
-        assert_eq!(Harmonic::Custom(7).to_number(), 7);
+        assert_eq!(Harmonic::from_number(7).unwrap().to_number(), 7);
     }
     
     #[test]
@@ -116,7 +141,7 @@ mod tests {
         assert_eq!(Harmonic::Fourth.angle_span(), 90.0);
         assert_eq!(Harmonic::Eighth.angle_span(), 45.0);
         assert_eq!(Harmonic::Sixteenth.angle_span(), 22.5);
-        assert_eq!(Harmonic::Custom(12).angle_span(), 30.0);
+        assert_eq!(Harmonic::from_number(12).unwrap().angle_span(), 30.0);
     }
     
     #[test]
@@ -126,7 +151,37 @@ mod tests {
         assert_eq!(Harmonic::Fourth.arm_count(), 16);
         assert_eq!(Harmonic::Eighth.arm_count(), 32);
         assert_eq!(Harmonic::Sixteenth.arm_count(), 64);
-        assert_eq!(Harmonic::Custom(3).arm_count(), 12);
+        assert_eq!(Harmonic::from_number(3).unwrap().arm_count(), 12);
+        assert_eq!(Harmonic::from_number(100).unwrap().arm_count(), 400);
+        assert_eq!(Harmonic::from_number(Harmonic::MAX_NUMBER).unwrap().arm_count(), u32::MAX - 3);
+    }
+    
+    #[test]
+    fn test_arm_angles() {
+        let angles: Vec<f64> = Harmonic::from_number(5).unwrap().arm_angles().collect();
+        assert_eq!(angles.len(), 20);
+        assert_eq!(angles[0], 0.0);
+        assert_eq!(angles[1], 18.0);
+        assert_eq!(angles[4], 72.0);
+    }
+    
+    #[test]
+    fn test_invalid_numbers() {
+        // Named harmonics are never custom, and there is no harmonic 0
+        assert_eq!(Harmonic::from_number(4), Some(Harmonic::Fourth));
+        assert_eq!(Harmonic::from_number(0), None);
+        assert_eq!(Harmonic::from_number(Harmonic::MAX_NUMBER + 1), None);
+        assert_ne!(Harmonic::from_number(5), Harmonic::from_number(7));
+        assert_eq!(Harmonic::from_number(1000).unwrap().to_number(), 1000);
+    }
+    
+    #[test]
+    fn test_display_name() {
+        assert_eq!(Harmonic::from_number(3).unwrap().display_name(), "3rd (120°00')");
+        assert_eq!(Harmonic::from_number(5).unwrap().display_name(), "5th (72°00')");
+        assert_eq!(Harmonic::from_number(7).unwrap().display_name(), "7th (51°25'43\")");
+        assert_eq!(Harmonic::from_number(11).unwrap().display_name(), "11th (32°43'38\")");
+        assert_eq!(Harmonic::from_number(22).unwrap().display_name(), "22nd (16°21'49\")");
     }
     
     #[test]
@@ -138,6 +193,12 @@ mod tests {
         
         // Test wrap-around
         assert_eq!(calculate_harmonic_position(90.0, Harmonic::Fourth), 0.0);
+        assert_eq!(calculate_harmonic_position(-10.0, Harmonic::Fourth), 320.0);
+        
+        // Odd and high harmonics
+        assert_eq!(calculate_harmonic_position(100.0, Harmonic::from_number(7).unwrap()), 340.0);
+        let high = calculate_harmonic_position(123.456, Harmonic::from_number(1000).unwrap());
+        assert!((high - 336.0).abs() < 0.01);
     }
     
     #[test]
@@ -146,5 +207,24 @@ mod tests {
         assert_eq!(calculate_original_position(60.0, Harmonic::Second), 30.0);
         assert_eq!(calculate_original_position(120.0, Harmonic::Fourth), 30.0);
         assert_eq!(calculate_original_position(240.0, Harmonic::Eighth), 30.0);
+        assert_eq!(calculate_original_position(340.0, Harmonic::from_number(7).unwrap()), 340.0 / 7.0);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_harmonic_round_trip() {
+        // Whole seconds stay exact through high harmonics and back
+        for harmonic in [1, 3, 7, 16, 90, 360, 1000].map(|n| Harmonic::from_number(n).unwrap()) {
+            for step in 0..500 {
+                let position = DegreePosition::from_arcseconds(step * 2591 + 17);
+                let expected = position.to_harmonic(harmonic.to_number());
+                let harmonic_position = calculate_harmonic_position(position.to_degrees(), harmonic);
+                assert_eq!(DegreePosition::from_degrees(harmonic_position), expected);
+                
+                let original = calculate_original_position(harmonic_position, harmonic);
+                assert!(original < harmonic.angle_span());
+                let offset = (position.to_degrees() - original).rem_euclid(harmonic.angle_span());
+                assert!(offset.min(harmonic.angle_span() - offset) < 1e-9);
+            }
+        }
+    }
+}
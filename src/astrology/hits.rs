@@ -0,0 +1,197 @@
+//! What the arms of the dial point at: chart points and midpoints of every
+//! ring lying within an orb of any arm for the current position and harmonic.
+
+use std::cmp::Ordering;
+
+use crate::astrology::antiscia::Mirror;
+use crate::astrology::chart::{ChartPoint, PointId};
+use crate::astrology::midpoints::{all_midpoints, Midpoint, MidpointKind};
+use crate::astrology::{DegreePosition, Harmonic};
+
+/// Something found under an arm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HitTarget {
+    Point(PointId),
+    Midpoint(Midpoint),
+}
+
+impl HitTarget {
+    /// Returns the point or pair in Uranian notation, e.g. "SU" or "SU/MO"
+    pub fn label(&self) -> String {
+        match self {
+            HitTarget::Point(point) => point.abbreviation().to_string(),
+            HitTarget::Midpoint(midpoint) => midpoint.label(),
+        }
+    }
+}
+
+/// A point or midpoint within orb of one of the arms
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArmHit {
+    /// Index of the ring, i.e. the chart, the target belongs to
+    pub ring: usize,
+    pub target: HitTarget,
+    /// Ecliptic longitude of the target in degrees
+    pub longitude: f64,
+    /// Angle of the arm from the red arm in degrees [0, 360)
+    pub arm: f64,
+    /// Distance from the arm in degrees
+    pub orb: f64,
+    /// Set when the mirror image of the target lies under the arm rather than the target
+    pub mirror: Option<Mirror>,
+}
+
+impl ArmHit {
+    /// Returns the target in Uranian notation, e.g. "SU/MO", or "SU/MO(A)"
+    /// for the antiscion of the midpoint
+    pub fn label(&self) -> String {
+        let mirror = self.mirror.map_or(String::new(), |mirror| format!("({})", mirror.abbreviation()));
+        format!("{}{}", self.target.label(), mirror)
+    }
+
+
+    /// Returns the arm's angle from the red arm as degrees, minutes and seconds
+    pub fn arm_position(&self) -> DegreePosition {
+        DegreePosition::from_degrees(self.arm)
+    }
+}
+
+/// Returns the points and midpoints of every ring within an orb of any arm,
+/// nearest first. Midpoints are taken within each ring's own chart.
+///
+/// The harmonic dial has four arms, so the arms fall every 90°/n of longitude
+/// from the position. Far midpoints lie 180° from the near ones, always on
+/// another arm, so only near midpoints are reported.
+///
+/// With antiscia on, the antiscia of the points and midpoints are read as
+/// well. The contra-antiscion lies opposite the antiscion, so on another arm,
+/// and is left out for the same reason.
+pub fn arm_hits(
+    rings: &[&[ChartPoint]],
+    position: f64,
+    harmonic: Harmonic,
+    orb: f64,
+    antiscia: bool,
+) -> Vec<ArmHit> {
+    let step = 90.0 / harmonic.to_number() as f64;
+    let mirrors: &[Option<Mirror>] = if antiscia {
+        &[None, Some(Mirror::Antiscion)]
+    } else {
+        &[None]
+    };
+    let arm_hit = |ring: usize, target: HitTarget, longitude: f64, mirror: Option<Mirror>| {
+        let longitude = mirror.map_or(longitude, |mirror| mirror.reflect(longitude));
+        let offset = (longitude - position).rem_euclid(360.0);
+        let arm = (offset / step).round() * step;
+        let distance = (offset - arm).abs();
+        (distance <= orb).then(|| ArmHit {
+            ring,
+            target,
+            longitude,
+            arm: arm.rem_euclid(360.0),
+            orb: distance,
+            mirror,
+        })
+    };
+
+    let mut hits: Vec<ArmHit> = Vec::new();
+    for (ring, points) in rings.iter().enumerate() {
+        let targets: Vec<(HitTarget, f64)> = points
+            .iter()
+            .map(|point| (HitTarget::Point(point.id), point.longitude))
+            .chain(
+                all_midpoints(points)
+                    .into_iter()
+                    .filter(|midpoint| midpoint.kind == MidpointKind::Near)
+                    .map(|midpoint| (HitTarget::Midpoint(midpoint), midpoint.longitude)),
+            )
+            .collect();
+        for &mirror in mirrors {
+            hits.extend(
+                targets
+                    .iter()
+                    .filter_map(|&(target, longitude)| arm_hit(ring, target, longitude, mirror)),
+            );
+        }
+    }
+    hits.sort_by(|a, b| a.orb.partial_cmp(&b.orb).unwrap_or(Ordering::Equal));
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::Body;
+
+    fn point(body: Body, longitude: f64) -> ChartPoint {
+        ChartPoint {
+            id: PointId::Body(body),
+            longitude,
+            latitude: 0.0,
+            speed: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_points_on_main_arms() {
+        let points = [point(Body::Sun, 10.5), point(Body::Moon, 279.0), point(Body::Mars, 55.0)];
+        let hits = arm_hits(&[&points], 10.0, Harmonic::First, 1.0, false);
+        let labels: Vec<String> = hits.iter().map(|hit| hit.target.label()).collect();
+        assert_eq!(labels, ["SU", "MO"]);
+        assert!((hits[0].orb - 0.5).abs() < 1e-9);
+        assert_eq!(hits[0].arm, 0.0);
+        assert_eq!(hits[1].arm, 270.0);
+    }
+
+    #[test]
+    fn test_midpoints_and_harmonic_arms() {
+        // SU/MO falls at 40°, which is an arm of the 4th harmonic from 17°30'
+        let points = [point(Body::Sun, 0.0), point(Body::Moon, 80.0)];
+        let hits = arm_hits(&[&points], 17.5, Harmonic::Fourth, 0.5, false);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].target.label(), "SU/MO");
+        assert!((hits[0].arm - 22.5).abs() < 1e-9);
+        assert!(arm_hits(&[&points], 17.5, Harmonic::First, 0.5, false).is_empty());
+    }
+
+    #[test]
+    fn test_all_rings() {
+        // A transit Saturn on the third ring lies under the 4th harmonic arm at 22°30'
+        let natal = [point(Body::Sun, 100.0)];
+        let transits = [point(Body::Saturn, 40.2)];
+        let hits = arm_hits(&[&natal, &[], &transits], 17.5, Harmonic::Fourth, 0.5, false);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].ring, 2);
+        assert_eq!(hits[0].target, HitTarget::Point(PointId::Body(Body::Saturn)));
+        assert!((hits[0].arm - 22.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_antiscia() {
+        // The antiscion of 10° Gemini is 20° Cancer, under the red arm
+        let points = [point(Body::Venus, 70.0), point(Body::Mars, 170.0)];
+        assert!(arm_hits(&[&points], 110.0, Harmonic::First, 0.5, false).is_empty());
+        let hits = arm_hits(&[&points], 110.0, Harmonic::First, 0.5, true);
+        let labels: Vec<String> = hits.iter().map(ArmHit::label).collect();
+        assert_eq!(labels, ["VE(A)"]);
+        assert_eq!(hits[0].mirror, Some(Mirror::Antiscion));
+        assert!((hits[0].longitude - 110.0).abs() < 1e-9);
+        assert_eq!(hits[0].arm, 0.0);
+
+        // VE/MA at 135° mirrors to 45°, under the arm at 270° when the red arm is on the midpoint
+        let points = [point(Body::Venus, 70.0), point(Body::Mars, 200.0)];
+        let hits = arm_hits(&[&points], 135.0, Harmonic::First, 0.5, true);
+        let labels: Vec<String> = hits.iter().map(ArmHit::label).collect();
+        assert_eq!(labels, ["VE/MA", "VE/MA(A)"]);
+        assert_eq!(hits[1].arm, 270.0);
+    }
+
+    #[test]
+    fn test_wraps_past_zero() {
+        let points = [point(Body::Venus, 359.5)];
+        let hits = arm_hits(&[&points], 0.2, Harmonic::First, 1.0, false);
+        assert_eq!(hits.len(), 1);
+        assert!((hits[0].orb - 0.7).abs() < 1e-9);
+        assert_eq!(hits[0].arm, 0.0);
+    }
+}
@@ -0,0 +1,723 @@
+//! The angles of the chart and the house systems built on them.
+//!
+//! The Hamburg School uses Meridian houses, which divide the equator into
+//! twelve equal parts starting at the RAMC, and equal houses measured from
+//! the Ascendant, the Aries Point, the Sun, the Moon or the Node. The common
+//! quadrant and equal systems are provided for comparison with other programs.
+
+use std::fmt;
+
+use crate::astrology::chart::{BirthData, ChartPoint, PointId};
+use crate::astrology::{time, Body};
+
+/// Latitudes closer than this to a pole have no defined horizon direction
+const POLE_TOLERANCE: f64 = 1e-9;
+
+/// A way of dividing the chart into twelve houses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HouseSystem {
+    /// Equal divisions of the equator from the RAMC, projected onto the ecliptic
+    #[default]
+    Meridian,
+    /// 30° houses from the Ascendant
+    Ascendant,
+    /// 30° houses from 0° Aries
+    Aries,
+    /// 30° houses from the Sun
+    Sun,
+    /// 30° houses from the Moon
+    Moon,
+    /// 30° houses from the Moon's node
+    Node,
+    /// Trisection of the semi-arcs of each degree
+    Placidus,
+    /// Trisection of the MC's semi-arc in time, the "birthplace" system
+    Koch,
+    /// Equal divisions of the equator, projected through the north and south points
+    Regiomontanus,
+    /// Equal divisions of the prime vertical, projected through the north and south points
+    Campanus,
+    /// 30° houses from the Ascendant, the same cusps as `Ascendant`
+    Equal,
+    /// Each sign is a house, the first being the sign on the Ascendant
+    WholeSign,
+}
+
+impl HouseSystem {
+    /// The six house systems of the Hamburg School
+    pub const URANIAN: [HouseSystem; 6] = [
+        Self::Meridian,
+        Self::Ascendant,
+        Self::Aries,
+        Self::Sun,
+        Self::Moon,
+        Self::Node,
+    ];
+
+    /// The common quadrant and equal systems used elsewhere
+    pub const CONVENTIONAL: [HouseSystem; 6] = [
+        Self::Placidus,
+        Self::Koch,
+        Self::Regiomontanus,
+        Self::Campanus,
+        Self::Equal,
+        Self::WholeSign,
+    ];
+
+    /// Every house system, Uranian ones first
+    pub const ALL: [HouseSystem; 12] = [
+        Self::Meridian,
+        Self::Ascendant,
+        Self::Aries,
+        Self::Sun,
+        Self::Moon,
+        Self::Node,
+        Self::Placidus,
+        Self::Koch,
+        Self::Regiomontanus,
+        Self::Campanus,
+        Self::Equal,
+        Self::WholeSign,
+    ];
+
+    /// Returns a display name for this house system
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Meridian => "Meridian",
+            Self::Ascendant => "Ascendant",
+            Self::Aries => "Aries",
+            Self::Sun => "Sun",
+            Self::Moon => "Moon",
+            Self::Node => "Node",
+            Self::Placidus => "Placidus",
+            Self::Koch => "Koch",
+            Self::Regiomontanus => "Regiomontanus",
+            Self::Campanus => "Campanus",
+            Self::Equal => "Equal",
+            Self::WholeSign => "Whole Sign",
+        }
+    }
+
+    /// The chart point the first house starts from, for the equal point-based systems
+    fn starting_point(&self) -> Option<PointId> {
+        match self {
+            Self::Sun => Some(PointId::Body(Body::Sun)),
+            Self::Moon => Some(PointId::Body(Body::Moon)),
+            Self::Node => Some(PointId::Node),
+            _ => None,
+        }
+    }
+}
+
+/// Errors raised when houses cannot be calculated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HouseError {
+    /// The latitude is outside [-90°, 90°]
+    InvalidLatitude(f64),
+    /// The ecliptic lies in the horizon, or the place is at a pole, so no
+    /// point of the ecliptic is rising
+    AscendantUndefined { latitude: f64 },
+    /// A point-based system needs a point the chart does not have
+    MissingPoint(PointId),
+    /// Some degrees never rise or set, so their semi-arcs cannot be divided
+    PolarCircle { system: HouseSystem, latitude: f64 },
+}
+
+impl fmt::Display for HouseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLatitude(latitude) => write!(f, "invalid latitude {}", latitude),
+            Self::AscendantUndefined { latitude } => {
+                write!(f, "the Ascendant is undefined at latitude {}", latitude)
+            }
+            Self::MissingPoint(point) => write!(f, "the chart has no {}", point.name()),
+            Self::PolarCircle { system, latitude } => write!(
+                f,
+                "{} houses are undefined inside the polar circle (latitude {})",
+                system.name(),
+                latitude
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HouseError {}
+
+/// The local frame in which houses are cast, all angles in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HouseFrame {
+    /// Right ascension of the MC, i.e. local apparent sidereal time
+    pub ramc: f64,
+    /// True obliquity of the ecliptic
+    pub obliquity: f64,
+    /// Geographic latitude, north positive
+    pub latitude: f64,
+}
+
+impl HouseFrame {
+    /// Creates a frame, checking the latitude
+    pub fn new(ramc: f64, obliquity: f64, latitude: f64) -> Result<Self, HouseError> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(HouseError::InvalidLatitude(latitude));
+        }
+        Ok(Self {
+            ramc: ramc.rem_euclid(360.0),
+            obliquity,
+            latitude,
+        })
+    }
+
+    /// The frame for a birth moment and place
+    pub fn for_birth(birth: &BirthData) -> Result<Self, HouseError> {
+        let jd = birth.julian_day();
+        Self::new(
+            time::local_apparent_sidereal_time(jd, birth.longitude),
+            time::obliquity(jd),
+            birth.latitude,
+        )
+    }
+
+    /// Ecliptic longitude of the upper meridian
+    pub fn midheaven(&self) -> f64 {
+        midheaven(self.ramc, self.obliquity)
+    }
+
+    /// Ecliptic longitude rising on the eastern horizon
+    pub fn ascendant(&self) -> Result<f64, HouseError> {
+        ascendant(self.ramc, self.obliquity, self.latitude)
+    }
+
+    /// The Ascendant for another RAMC and pole height, as used by quadrant systems
+    fn ascendant_for(&self, ramc: f64, pole: f64) -> Result<f64, HouseError> {
+        ascendant(ramc, self.obliquity, pole)
+    }
+}
+
+/// Ecliptic longitude culminating at a right ascension of the MC, all in degrees
+pub fn midheaven(ramc: f64, obliquity: f64) -> f64 {
+    let (sin_t, cos_t) = ramc.to_radians().sin_cos();
+    sin_t
+        .atan2(cos_t * obliquity.to_radians().cos())
+        .to_degrees()
+        .rem_euclid(360.0)
+}
+
+/// Ecliptic longitude rising on the eastern horizon, all in degrees
+pub fn ascendant(ramc: f64, obliquity: f64, latitude: f64) -> Result<f64, HouseError> {
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(HouseError::InvalidLatitude(latitude));
+    }
+    if 90.0 - latitude.abs() < POLE_TOLERANCE {
+        return Err(HouseError::AscendantUndefined { latitude });
+    }
+
+    let (sin_t, cos_t) = ramc.to_radians().sin_cos();
+    let (sin_e, cos_e) = obliquity.to_radians().sin_cos();
+    let y = cos_t;
+    let x = -(sin_t * cos_e + latitude.to_radians().tan() * sin_e);
+    if y.hypot(x) < POLE_TOLERANCE {
+        return Err(HouseError::AscendantUndefined { latitude });
+    }
+    Ok(y.atan2(x).to_degrees().rem_euclid(360.0))
+}
+
+/// Twelve house cusps as ecliptic longitudes in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Houses {
+    pub system: HouseSystem,
+    cusps: [f64; 12],
+}
+
+impl Houses {
+    /// Houses of 30° each starting from a longitude
+    pub fn equal(system: HouseSystem, start: f64) -> Self {
+        let mut cusps = [0.0; 12];
+        for (i, cusp) in cusps.iter_mut().enumerate() {
+            *cusp = (start + 30.0 * i as f64).rem_euclid(360.0);
+        }
+        Self { system, cusps }
+    }
+
+    /// All cusps, from the first house to the twelfth
+    pub fn cusps(&self) -> &[f64; 12] {
+        &self.cusps
+    }
+
+    /// Longitude of the cusp of a house numbered 1 to 12
+    pub fn cusp(&self, house: usize) -> f64 {
+        assert!((1..=12).contains(&house), "house {} out of range", house);
+        self.cusps[house - 1]
+    }
+
+    /// Returns the house (1 to 12) containing a longitude
+    pub fn house_of(&self, longitude: f64) -> usize {
+        let longitude = longitude.rem_euclid(360.0);
+        (0..12)
+            .find(|&i| {
+                let start = self.cusps[i];
+                let width = (self.cusps[(i + 1) % 12] - start).rem_euclid(360.0);
+                (longitude - start).rem_euclid(360.0) < width
+            })
+            .map_or(1, |i| i + 1)
+    }
+}
+
+/// Calculates the houses of a system. The points supply the Sun, Moon and Node
+/// for the systems that start from them.
+pub fn calculate(system: HouseSystem, frame: &HouseFrame, points: &[ChartPoint]) -> Result<Houses, HouseError> {
+    match system {
+        HouseSystem::Meridian => Ok(meridian(frame)),
+        HouseSystem::Ascendant => Ok(Houses::equal(system, frame.ascendant()?)),
+        HouseSystem::Aries => Ok(Houses::equal(system, 0.0)),
+        HouseSystem::Sun | HouseSystem::Moon | HouseSystem::Node => {
+            let id = system.starting_point().expect("point-based system");
+            let point = points
+                .iter()
+                .find(|point| point.id == id)
+                .ok_or(HouseError::MissingPoint(id))?;
+            Ok(Houses::equal(system, point.longitude))
+        }
+        HouseSystem::Placidus => placidus(frame),
+        HouseSystem::Koch => koch(frame),
+        HouseSystem::Regiomontanus => regiomontanus(frame),
+        HouseSystem::Campanus => campanus(frame),
+        HouseSystem::Equal => Ok(Houses::equal(system, frame.ascendant()?)),
+        HouseSystem::WholeSign => {
+            let ascendant = frame.ascendant()?;
+            Ok(Houses::equal(system, (ascendant / 30.0).floor() * 30.0))
+        }
+    }
+}
+
+impl Houses {
+    /// Builds quadrant houses from the MC, Ascendant and the four intermediate
+    /// cusps in the order 11, 12, 2, 3; the rest are their opposites
+    fn quadrant(system: HouseSystem, midheaven: f64, ascendant: f64, intermediate: [f64; 4]) -> Self {
+        let mut cusps = [0.0; 12];
+        let eastern = [
+            (10, midheaven),
+            (11, intermediate[0]),
+            (12, intermediate[1]),
+            (1, ascendant),
+            (2, intermediate[2]),
+            (3, intermediate[3]),
+        ];
+        for (house, longitude) in eastern {
+            cusps[house - 1] = longitude.rem_euclid(360.0);
+            cusps[(house + 5) % 12] = (longitude + 180.0).rem_euclid(360.0);
+        }
+        Self { system, cusps }
+    }
+}
+
+/// Placidus: each cusp is the degree that has covered a third or two thirds of
+/// its diurnal (or nocturnal) semi-arc, found by iteration
+fn placidus(frame: &HouseFrame) -> Result<Houses, HouseError> {
+    let ascendant = frame.ascendant()?;
+    let polar = HouseError::PolarCircle {
+        system: HouseSystem::Placidus,
+        latitude: frame.latitude,
+    };
+    let tan_phi = frame.latitude.to_radians().tan();
+    let sin_e = frame.obliquity.to_radians().sin();
+
+    // Fraction of the semi-arc and whether the cusp is above the horizon
+    let sections = [(1.0 / 3.0, true), (2.0 / 3.0, true), (2.0 / 3.0, false), (1.0 / 3.0, false)];
+    let mut intermediate = [0.0; 4];
+    for (cusp, (fraction, above)) in intermediate.iter_mut().zip(sections) {
+        let offset_for = |ascensional_difference: f64| {
+            if above {
+                fraction * (90.0 + ascensional_difference)
+            } else {
+                180.0 - fraction * (90.0 - ascensional_difference)
+            }
+        };
+
+        let mut right_ascension = frame.ramc + offset_for(0.0);
+        for _ in 0..100 {
+            let longitude = midheaven(right_ascension, frame.obliquity);
+            let declination = (sin_e * longitude.to_radians().sin()).asin();
+            let x = tan_phi * declination.tan();
+            if x.abs() > 1.0 {
+                return Err(polar);
+            }
+            let next = frame.ramc + offset_for(x.asin().to_degrees());
+            let converged = (next - right_ascension).abs() < 1e-10;
+            right_ascension = next;
+            if converged {
+                break;
+            }
+        }
+        *cusp = midheaven(right_ascension, frame.obliquity);
+    }
+
+    Ok(Houses::quadrant(HouseSystem::Placidus, frame.midheaven(), ascendant, intermediate))
+}
+
+/// Koch: the cusps are the degrees rising when the MC degree had covered a
+/// third and two thirds of its diurnal semi-arc, and their continuation below
+/// the horizon
+fn koch(frame: &HouseFrame) -> Result<Houses, HouseError> {
+    let ascendant = frame.ascendant()?;
+    let midheaven = frame.midheaven();
+    let sin_e = frame.obliquity.to_radians().sin();
+    let declination = (sin_e * midheaven.to_radians().sin()).asin();
+    let x = frame.latitude.to_radians().tan() * declination.tan();
+    if x.abs() > 1.0 {
+        return Err(HouseError::PolarCircle {
+            system: HouseSystem::Koch,
+            latitude: frame.latitude,
+        });
+    }
+    let step = (90.0 + x.asin().to_degrees()) / 3.0;
+
+    let mut intermediate = [0.0; 4];
+    for (cusp, steps) in intermediate.iter_mut().zip([-2.0, -1.0, 1.0, 2.0]) {
+        *cusp = frame.ascendant_for(frame.ramc + steps * step, frame.latitude)?;
+    }
+    Ok(Houses::quadrant(HouseSystem::Koch, midheaven, ascendant, intermediate))
+}
+
+/// Regiomontanus: house circles through the north and south points cut the
+/// equator every 30° from the meridian
+fn regiomontanus(frame: &HouseFrame) -> Result<Houses, HouseError> {
+    let ascendant = frame.ascendant()?;
+    let tan_phi = frame.latitude.to_radians().tan();
+
+    let mut intermediate = [0.0; 4];
+    for (cusp, sections) in intermediate.iter_mut().zip([1.0, 2.0, 4.0, 5.0]) {
+        let equator: f64 = 30.0 * sections;
+        let pole = (tan_phi * equator.to_radians().sin()).atan().to_degrees();
+        *cusp = frame.ascendant_for(frame.ramc + equator - 90.0, pole)?;
+    }
+    Ok(Houses::quadrant(HouseSystem::Regiomontanus, frame.midheaven(), ascendant, intermediate))
+}
+
+/// Campanus: house circles through the north and south points cut the prime
+/// vertical every 30° from the zenith
+fn campanus(frame: &HouseFrame) -> Result<Houses, HouseError> {
+    let ascendant = frame.ascendant()?;
+    let (sin_phi, cos_phi) = frame.latitude.to_radians().sin_cos();
+
+    let mut intermediate = [0.0; 4];
+    for (cusp, sections) in intermediate.iter_mut().zip([1.0, 2.0, 4.0, 5.0]) {
+        let (sin_a, cos_a) = (30.0_f64 * sections).to_radians().sin_cos();
+        let equator = (sin_a * cos_phi).atan2(cos_a).to_degrees();
+        let pole = (sin_phi * sin_a).asin().to_degrees();
+        *cusp = frame.ascendant_for(frame.ramc + equator - 90.0, pole)?;
+    }
+    Ok(Houses::quadrant(HouseSystem::Campanus, frame.midheaven(), ascendant, intermediate))
+}
+
+/// Meridian houses: the tenth cusp is the MC and each following cusp culminates
+/// two hours of sidereal time later
+fn meridian(frame: &HouseFrame) -> Houses {
+    let mut cusps = [0.0; 12];
+    for (i, cusp) in cusps.iter_mut().enumerate() {
+        let house = i as f64 + 1.0;
+        *cusp = midheaven(frame.ramc + 30.0 * (house - 10.0), frame.obliquity);
+    }
+    Houses {
+        system: HouseSystem::Meridian,
+        cusps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OBLIQUITY: f64 = 23.4393;
+
+    fn arcminutes(a: f64, b: f64) -> f64 {
+        ((a - b + 180.0).rem_euclid(360.0) - 180.0).abs() * 60.0
+    }
+
+    #[test]
+    fn test_angles_at_equator() {
+        assert!(arcminutes(midheaven(0.0, OBLIQUITY), 0.0) < 1e-6);
+        assert!(arcminutes(ascendant(0.0, OBLIQUITY, 0.0).unwrap(), 90.0) < 1e-6);
+        assert!(arcminutes(midheaven(90.0, OBLIQUITY), 90.0) < 1e-6);
+        assert!(arcminutes(ascendant(90.0, OBLIQUITY, 0.0).unwrap(), 180.0) < 1e-6);
+    }
+
+    #[test]
+    fn test_ascendant_london() {
+        // Tables of houses for London (51°32'N): sidereal time 0h, Ascendant 26°36' Cancer
+        let asc = ascendant(0.0, OBLIQUITY, 51.0 + 32.0 / 60.0).unwrap();
+        assert!(arcminutes(asc, 116.6) < 2.0);
+    }
+
+    #[test]
+    fn test_polar_errors() {
+        assert_eq!(
+            ascendant(0.0, OBLIQUITY, 90.0),
+            Err(HouseError::AscendantUndefined { latitude: 90.0 })
+        );
+        // Inside the polar circle the ecliptic coincides with the horizon once a day
+        let latitude = 90.0 - OBLIQUITY;
+        assert!(matches!(
+            ascendant(270.0, OBLIQUITY, latitude),
+            Err(HouseError::AscendantUndefined { .. })
+        ));
+        assert_eq!(
+            HouseFrame::new(0.0, OBLIQUITY, 91.0),
+            Err(HouseError::InvalidLatitude(91.0))
+        );
+    }
+
+    #[test]
+    fn test_meridian_houses() {
+        let frame = HouseFrame::new(40.0, OBLIQUITY, 53.55).unwrap();
+        let houses = calculate(HouseSystem::Meridian, &frame, &[]).unwrap();
+        assert!(arcminutes(houses.cusp(10), frame.midheaven()) < 1e-6);
+        assert!(arcminutes(houses.cusp(4), frame.midheaven() + 180.0) < 1e-6);
+        // Opposite houses have opposite cusps
+        for house in 1..=6 {
+            assert!(arcminutes(houses.cusp(house) + 180.0, houses.cusp(house + 6)) < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_meridian_houses_ignore_latitude() {
+        let north = HouseFrame::new(123.0, OBLIQUITY, 80.0).unwrap();
+        let south = HouseFrame::new(123.0, OBLIQUITY, -80.0).unwrap();
+        assert_eq!(meridian(&north).cusps(), meridian(&south).cusps());
+    }
+
+    #[test]
+    fn test_point_based_houses() {
+        let frame = HouseFrame::new(0.0, OBLIQUITY, 0.0).unwrap();
+        let sun = ChartPoint {
+            id: PointId::Body(Body::Sun),
+            longitude: 280.5,
+            latitude: 0.0,
+            speed: 1.0,
+        };
+        let houses = calculate(HouseSystem::Sun, &frame, &[sun]).unwrap();
+        assert_eq!(houses.cusp(1), 280.5);
+        assert_eq!(houses.cusp(3), 340.5);
+        assert_eq!(houses.cusp(4), 10.5);
+
+        assert_eq!(
+            calculate(HouseSystem::Moon, &frame, &[sun]),
+            Err(HouseError::MissingPoint(PointId::Body(Body::Moon)))
+        );
+        assert_eq!(calculate(HouseSystem::Aries, &frame, &[]).unwrap().cusp(5), 120.0);
+    }
+
+    #[test]
+    fn test_house_of() {
+        let houses = Houses::equal(HouseSystem::Ascendant, 350.0);
+        assert_eq!(houses.house_of(355.0), 1);
+        assert_eq!(houses.house_of(19.9), 1);
+        assert_eq!(houses.house_of(20.0), 2);
+        assert_eq!(houses.house_of(349.0), 12);
+    }
+
+    #[test]
+    fn test_placidus_london() {
+        // Raphael's tables of houses for London (51°32'N), sidereal time 0h:
+        // 11th 9° Taurus, 12th 22° Gemini, Ascendant 26°36' Cancer, 2nd 12° Leo,
+        // 3rd 3° Virgo. The Ascendant is printed to the minute, the other cusps
+        // only to the whole degree.
+        let frame = HouseFrame::new(0.0, OBLIQUITY, 51.0 + 32.0 / 60.0).unwrap();
+        let houses = calculate(HouseSystem::Placidus, &frame, &[]).unwrap();
+        assert!(arcminutes(houses.cusp(10), 0.0) < 1e-6);
+        assert!(arcminutes(houses.cusp(1), 116.6) < 0.5);
+        for (house, degree) in [(11, 39.0), (12, 82.0), (2, 132.0), (3, 153.0)] {
+            assert!(arcminutes(houses.cusp(house), degree) < 60.0, "house {}", house);
+        }
+    }
+
+    /// Unit vector of an ecliptic longitude in equatorial coordinates
+    fn ecliptic(longitude: f64, obliquity: f64) -> [f64; 3] {
+        let (sin_l, cos_l) = longitude.to_radians().sin_cos();
+        let (sin_e, cos_e) = obliquity.to_radians().sin_cos();
+        [cos_l, sin_l * cos_e, sin_l * sin_e]
+    }
+
+    /// Unit vector of a right ascension and declination
+    fn equatorial(right_ascension: f64, declination: f64) -> [f64; 3] {
+        let (sin_a, cos_a) = right_ascension.to_radians().sin_cos();
+        let (sin_d, cos_d) = declination.to_radians().sin_cos();
+        [cos_d * cos_a, cos_d * sin_a, sin_d]
+    }
+
+    fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+
+    /// Frames at several sidereal times and latitudes in both hemispheres
+    fn frames() -> Vec<HouseFrame> {
+        let mut frames = Vec::new();
+        for ramc in [0.0, 47.0, 133.0, 200.0, 315.0] {
+            for latitude in [-52.0, -23.0, 12.0, 51.5, 60.0] {
+                frames.push(HouseFrame::new(ramc, OBLIQUITY, latitude).unwrap());
+            }
+        }
+        frames
+    }
+
+    /// The intermediate cusps in the order 11, 12, 2, 3 with their sections
+    /// counted eastwards from the upper meridian
+    const INTERMEDIATE: [(usize, f64); 4] = [(11, 1.0), (12, 2.0), (2, 4.0), (3, 5.0)];
+
+    #[test]
+    fn test_regiomontanus_definition() {
+        // Each cusp lies on the great circle through the north point of the
+        // horizon and the equator 30° per house east of the meridian
+        for frame in frames() {
+            let houses = calculate(HouseSystem::Regiomontanus, &frame, &[]).unwrap();
+            let zenith = equatorial(frame.ramc, frame.latitude);
+            let north = equatorial(frame.ramc + 180.0, 90.0 - frame.latitude);
+            assert!(dot(zenith, north).abs() < 1e-12);
+            for (house, sections) in INTERMEDIATE {
+                let equator = equatorial(frame.ramc + 30.0 * sections, 0.0);
+                let cusp = ecliptic(houses.cusp(house), frame.obliquity);
+                assert!(dot(cusp, cross(north, equator)).abs() < 1e-9, "{:?} house {}", frame, house);
+            }
+        }
+    }
+
+    #[test]
+    fn test_campanus_definition() {
+        // Each cusp lies on the great circle through the north point of the
+        // horizon and the prime vertical 30° per house east of the zenith
+        for frame in frames() {
+            let houses = calculate(HouseSystem::Campanus, &frame, &[]).unwrap();
+            let zenith = equatorial(frame.ramc, frame.latitude);
+            let north = equatorial(frame.ramc + 180.0, 90.0 - frame.latitude);
+            let east = equatorial(frame.ramc + 90.0, 0.0);
+            for (house, sections) in INTERMEDIATE {
+                let (sin_a, cos_a) = (30.0 * sections).to_radians().sin_cos();
+                let vertical = [0, 1, 2].map(|i| cos_a * zenith[i] + sin_a * east[i]);
+                let cusp = ecliptic(houses.cusp(house), frame.obliquity);
+                assert!(dot(cusp, cross(north, vertical)).abs() < 1e-9, "{:?} house {}", frame, house);
+            }
+        }
+    }
+
+    #[test]
+    fn test_placidus_definition() {
+        // Each cusp has covered its share of its own diurnal or nocturnal semi-arc
+        for frame in frames().into_iter().filter(|frame| frame.latitude.abs() < 60.0) {
+            let houses = calculate(HouseSystem::Placidus, &frame, &[]).unwrap();
+            for (house, sections) in INTERMEDIATE {
+                let [x, y, z] = ecliptic(houses.cusp(house), frame.obliquity);
+                let right_ascension = y.atan2(x).to_degrees();
+                let declination = z.asin();
+                let diurnal = (-frame.latitude.to_radians().tan() * declination.tan())
+                    .acos()
+                    .to_degrees();
+                let expected = if sections < 3.0 {
+                    sections / 3.0 * diurnal
+                } else {
+                    180.0 - (6.0 - sections) / 3.0 * (180.0 - diurnal)
+                };
+                let hour_angle = (right_ascension - frame.ramc).rem_euclid(360.0);
+                assert!(arcminutes(hour_angle, expected) < 1e-6, "{:?} house {}", frame, house);
+            }
+        }
+    }
+
+    #[test]
+    fn test_koch_definition() {
+        // Each cusp is on the eastern horizon at the moment the MC degree had
+        // covered a third or two thirds of its diurnal semi-arc
+        for frame in frames().into_iter().filter(|frame| frame.latitude.abs() < 60.0) {
+            let houses = calculate(HouseSystem::Koch, &frame, &[]).unwrap();
+            let [x, y, z] = ecliptic(frame.midheaven(), frame.obliquity);
+            let declination = z.atan2(x.hypot(y));
+            let diurnal = (-frame.latitude.to_radians().tan() * declination.tan())
+                .acos()
+                .to_degrees();
+            for (house, sections) in INTERMEDIATE {
+                let ramc = frame.ramc + (sections - 3.0) / 3.0 * diurnal;
+                let zenith = equatorial(ramc, frame.latitude);
+                let east = equatorial(ramc + 90.0, 0.0);
+                let cusp = ecliptic(houses.cusp(house), frame.obliquity);
+                assert!(dot(cusp, zenith).abs() < 1e-9, "{:?} house {}", frame, house);
+                assert!(dot(cusp, east) > 0.0, "{:?} house {}", frame, house);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quadrant_systems_agree_at_equator() {
+        let frame = HouseFrame::new(123.0, OBLIQUITY, 0.0).unwrap();
+        let meridian = meridian(&frame);
+        for system in [
+            HouseSystem::Placidus,
+            HouseSystem::Koch,
+            HouseSystem::Regiomontanus,
+            HouseSystem::Campanus,
+        ] {
+            let houses = calculate(system, &frame, &[]).unwrap();
+            for house in 1..=12 {
+                assert!(
+                    arcminutes(houses.cusp(house), meridian.cusp(house)) < 1e-6,
+                    "{:?} house {}",
+                    system,
+                    house
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_quadrant_cusps_in_order() {
+        for system in [
+            HouseSystem::Placidus,
+            HouseSystem::Koch,
+            HouseSystem::Regiomontanus,
+            HouseSystem::Campanus,
+        ] {
+            for ramc in [0.0, 77.0, 200.0, 315.0] {
+                let frame = HouseFrame::new(ramc, OBLIQUITY, 48.0).unwrap();
+                let houses = calculate(system, &frame, &[]).unwrap();
+                let total: f64 = (0..12)
+                    .map(|i| (houses.cusps()[(i + 1) % 12] - houses.cusps()[i]).rem_euclid(360.0))
+                    .sum();
+                assert!((total - 360.0).abs() < 1e-6, "{:?} at {}", system, ramc);
+            }
+        }
+    }
+
+    #[test]
+    fn test_equal_and_whole_sign() {
+        let frame = HouseFrame::new(0.0, OBLIQUITY, 51.5).unwrap();
+        let ascendant = frame.ascendant().unwrap();
+        let equal = calculate(HouseSystem::Equal, &frame, &[]).unwrap();
+        let uranian = calculate(HouseSystem::Ascendant, &frame, &[]).unwrap();
+        assert_eq!(equal.cusps(), uranian.cusps());
+
+        let whole = calculate(HouseSystem::WholeSign, &frame, &[]).unwrap();
+        assert_eq!(whole.cusp(1), 90.0);
+        assert!(ascendant > 90.0 && ascendant < 120.0);
+        assert_eq!(whole.cusp(12), 60.0);
+    }
+
+    #[test]
+    fn test_polar_circle() {
+        // With the MC in Cancer its degree never sets at 70°N
+        let frame = HouseFrame::new(90.0, OBLIQUITY, 70.0).unwrap();
+        for system in [HouseSystem::Placidus, HouseSystem::Koch] {
+            assert_eq!(
+                calculate(system, &frame, &[]),
+                Err(HouseError::PolarCircle { system, latitude: 70.0 })
+            );
+        }
+        // The projections through the north and south points work at any latitude
+        assert!(calculate(HouseSystem::Regiomontanus, &frame, &[]).is_ok());
+        assert!(calculate(HouseSystem::Campanus, &frame, &[]).is_ok());
+    }
+}
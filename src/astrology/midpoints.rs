@@ -0,0 +1,499 @@
+//! Midpoints of every pair of chart points and the sorted midpoint list.
+//!
+//! Each pair has a near midpoint, halfway along the shorter arc, and a far
+//! midpoint opposite it. On a dial whose span divides 180° the two coincide,
+//! so the list carries the far midpoint only where it falls elsewhere.
+
+use std::cmp::Ordering;
+
+use crate::astrology::antiscia::Mirror;
+use crate::astrology::chart::{ChartPoint, PointId};
+use crate::astrology::{DegreePosition, Harmonic};
+
+/// Positions closer than this (degrees) on the dial are treated as equal
+const EPSILON: f64 = 1e-9;
+
+/// Whether a midpoint lies on the shorter or longer arc between its pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MidpointKind {
+    Near,
+    Far,
+}
+
+/// The midpoint of two chart points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Midpoint {
+    pub first: PointId,
+    pub second: PointId,
+    pub kind: MidpointKind,
+    /// Ecliptic longitude in degrees [0, 360)
+    pub longitude: f64,
+}
+
+impl Midpoint {
+    /// Returns the pair in Uranian notation, e.g. "SU/MO"
+    pub fn label(&self) -> String {
+        format!("{}/{}", self.first.abbreviation(), self.second.abbreviation())
+    }
+
+    /// Whether either half of the pair is the given point
+    pub fn involves(&self, point: PointId) -> bool {
+        self.first == point || self.second == point
+    }
+
+    /// Position on the dial of a harmonic, in degrees within its span
+    pub fn reduced(&self, harmonic: Harmonic) -> f64 {
+        reduce(self.longitude, harmonic)
+    }
+}
+
+/// The near midpoint of two longitudes in degrees
+pub fn midpoint(a: f64, b: f64) -> f64 {
+    let half_arc = ((b - a).rem_euclid(360.0)) / 2.0;
+    if half_arc <= 90.0 {
+        (a + half_arc).rem_euclid(360.0)
+    } else {
+        (a + half_arc + 180.0).rem_euclid(360.0)
+    }
+}
+
+/// Span of a harmonic dial in degrees, kept in double precision
+fn span(harmonic: Harmonic) -> f64 {
+    360.0 / harmonic.to_number() as f64
+}
+
+/// Reduces a longitude to the span of a harmonic dial, e.g. modulo 90° on the 90° dial
+pub fn reduce(longitude: f64, harmonic: Harmonic) -> f64 {
+    let span = span(harmonic);
+    let reduced = longitude.rem_euclid(span);
+    // Values a hair below the span wrap round to zero
+    if span - reduced < EPSILON {
+        0.0
+    } else {
+        reduced
+    }
+}
+
+/// Computes the near and far midpoints of every pair of points
+pub fn all_midpoints(points: &[ChartPoint]) -> Vec<Midpoint> {
+    let mut midpoints = Vec::with_capacity(points.len() * points.len());
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            let near = midpoint(a.longitude, b.longitude);
+            for (kind, longitude) in [
+                (MidpointKind::Near, near),
+                (MidpointKind::Far, (near + 180.0).rem_euclid(360.0)),
+            ] {
+                midpoints.push(Midpoint {
+                    first: a.id,
+                    second: b.id,
+                    kind,
+                    longitude,
+                });
+            }
+        }
+    }
+    midpoints
+}
+
+/// A midpoint placed on a harmonic dial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MidpointEntry {
+    pub midpoint: Midpoint,
+    /// Position on the dial in degrees within the harmonic's span
+    pub position: f64,
+}
+
+impl MidpointEntry {
+    /// Returns the dial position as degrees, minutes and seconds
+    pub fn degree_position(&self) -> DegreePosition {
+        DegreePosition::from_degrees(self.position)
+    }
+}
+
+/// The classic sorted midpoint list for one harmonic dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidpointList {
+    pub harmonic: Harmonic,
+    entries: Vec<MidpointEntry>,
+}
+
+impl MidpointList {
+    /// Builds the list of all midpoints of the points, sorted by dial position
+    pub fn new(points: &[ChartPoint], harmonic: Harmonic) -> Self {
+        let span = span(harmonic);
+        let mut entries: Vec<MidpointEntry> = Vec::new();
+
+        for midpoint in all_midpoints(points) {
+            let position = midpoint.reduced(harmonic);
+            // The far midpoint only counts where it lands apart from the near one
+            if midpoint.kind == MidpointKind::Far {
+                let near = (position + 180.0).rem_euclid(span);
+                let apart = (position - near).abs();
+                if apart < EPSILON || span - apart < EPSILON {
+                    continue;
+                }
+            }
+            entries.push(MidpointEntry { midpoint, position });
+        }
+
+        entries.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap_or(Ordering::Equal));
+        Self { harmonic, entries }
+    }
+
+    /// All entries in dial order
+    pub fn entries(&self) -> &[MidpointEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries whose dial position lies within an orb of a position, nearest first
+    pub fn near(&self, position: f64, orb: f64) -> Vec<&MidpointEntry> {
+        let span = span(self.harmonic);
+        let distance = |entry: &MidpointEntry| {
+            let diff = (entry.position - position).rem_euclid(span);
+            diff.min(span - diff)
+        };
+        let mut found: Vec<&MidpointEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| distance(entry) <= orb)
+            .collect();
+        found.sort_by(|a, b| distance(a).partial_cmp(&distance(b)).unwrap_or(Ordering::Equal));
+        found
+    }
+}
+
+/// A midpoint falling on the focal point of a tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeEntry {
+    /// The near midpoint of the pair
+    pub midpoint: Midpoint,
+    /// Angle from the focal point to the midpoint the dial reads as a hit, e.g. 90° for a square
+    pub aspect: f64,
+    /// Distance from exactness in degrees
+    pub orb: f64,
+    /// Whether the midpoint is conjunct or opposite the focal point rather than
+    /// in a harder aspect
+    pub direct: bool,
+    /// Set when the midpoint falls on the focal point's mirror image rather than the point
+    pub mirror: Option<Mirror>,
+}
+
+/// All midpoints on one focal point within an orb, as read from a harmonic dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidpointTree {
+    pub focus: PointId,
+    pub harmonic: Harmonic,
+    pub orb: f64,
+    entries: Vec<TreeEntry>,
+}
+
+impl MidpointTree {
+    /// Builds the tree of a focal point, sorted by orb.
+    ///
+    /// Pairs containing the focal point itself are left out. Returns `None`
+    /// when the focal point is not among the points.
+    pub fn new(points: &[ChartPoint], focus: PointId, harmonic: Harmonic, orb: f64) -> Option<Self> {
+        let longitude = points.iter().find(|point| point.id == focus)?.longitude;
+        Some(Self::build(points, focus, longitude, true, harmonic, orb, &[None]))
+    }
+
+    /// Builds the tree of a focal point together with the midpoints on its
+    /// antiscion, sorted by orb.
+    ///
+    /// The contra-antiscion lies opposite the antiscion, so the tree, which
+    /// reads both midpoints of each pair, would find the same ones there.
+    /// Likewise an antiscion on the point's own axis adds nothing and is skipped.
+    pub fn with_antiscia(points: &[ChartPoint], focus: PointId, harmonic: Harmonic, orb: f64) -> Option<Self> {
+        let longitude = points.iter().find(|point| point.id == focus)?.longitude;
+        Some(Self::build(points, focus, longitude, true, harmonic, orb, &[None, Some(Mirror::Antiscion)]))
+    }
+
+    /// Builds the tree of a point from another chart, such as a transit or a
+    /// directed point, over the midpoints of these points. As the focal point
+    /// is not one of them, no pairs are left out.
+    pub fn of_outer_point(
+        points: &[ChartPoint],
+        focus: PointId,
+        longitude: f64,
+        harmonic: Harmonic,
+        orb: f64,
+        antiscia: bool,
+    ) -> Self {
+        let mirrors: &[Option<Mirror>] = if antiscia {
+            &[None, Some(Mirror::Antiscion)]
+        } else {
+            &[None]
+        };
+        Self::build(points, focus, longitude, false, harmonic, orb, mirrors)
+    }
+
+    fn build(
+        points: &[ChartPoint],
+        focus: PointId,
+        longitude: f64,
+        in_chart: bool,
+        harmonic: Harmonic,
+        orb: f64,
+        mirrors: &[Option<Mirror>],
+    ) -> Self {
+        let span = span(harmonic);
+
+        let mut foci: Vec<(Option<Mirror>, f64)> = Vec::new();
+        for &mirror in mirrors {
+            let focus_longitude = mirror.map_or(longitude, |mirror| mirror.reflect(longitude));
+            let same_axis = foci.iter().any(|&(_, other)| {
+                [0.0, 180.0].into_iter().any(|turn: f64| {
+                    let apart = (focus_longitude - other + turn).rem_euclid(span);
+                    apart < EPSILON || span - apart < EPSILON
+                })
+            });
+            if !same_axis {
+                foci.push((mirror, focus_longitude));
+            }
+        }
+
+        let midpoints: Vec<Midpoint> = all_midpoints(points)
+            .into_iter()
+            .filter(|midpoint| midpoint.kind == MidpointKind::Near && !(in_chart && midpoint.involves(focus)))
+            .collect();
+        let mut entries: Vec<TreeEntry> = foci
+            .iter()
+            .flat_map(|&(mirror, focus_longitude)| {
+                midpoints.iter().map(move |&midpoint| (mirror, focus_longitude, midpoint))
+            })
+            .filter_map(|(mirror, focus_longitude, midpoint)| {
+                let arc = (midpoint.longitude - focus_longitude).rem_euclid(360.0);
+                // The far midpoint may lie closer to an arm than the near one
+                let (turn, offset, hit) = [0.0, 180.0]
+                    .into_iter()
+                    .map(|turn: f64| {
+                        let arc = (arc + turn) % 360.0;
+                        let multiple = (arc / span).round() * span;
+                        (turn, arc - multiple, multiple)
+                    })
+                    .min_by(|a, b| a.1.abs().partial_cmp(&b.1.abs()).unwrap_or(Ordering::Equal))?;
+                if offset.abs() > orb {
+                    return None;
+                }
+
+                let aspect = (hit - turn).rem_euclid(360.0);
+                let from_axis = aspect % 180.0;
+                Some(TreeEntry {
+                    midpoint,
+                    aspect,
+                    orb: offset.abs(),
+                    direct: from_axis < EPSILON || 180.0 - from_axis < EPSILON,
+                    mirror,
+                })
+            })
+            .collect();
+
+        entries.sort_by(|a, b| a.orb.partial_cmp(&b.orb).unwrap_or(Ordering::Equal));
+        Self {
+            focus,
+            harmonic,
+            orb,
+            entries,
+        }
+    }
+
+    /// All entries, closest first
+    pub fn entries(&self) -> &[TreeEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::Body;
+
+    fn point(body: Body, longitude: f64) -> ChartPoint {
+        ChartPoint {
+            id: PointId::Body(body),
+            longitude,
+            latitude: 0.0,
+            speed: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(midpoint(10.0, 50.0), 30.0);
+        assert_eq!(midpoint(50.0, 10.0), 30.0);
+        // Across 0° Aries the near midpoint takes the short way round
+        assert_eq!(midpoint(350.0, 30.0), 10.0);
+        assert_eq!(midpoint(30.0, 350.0), 10.0);
+        assert_eq!(midpoint(0.0, 270.0), 315.0);
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(reduce(100.0, Harmonic::Fourth), 10.0);
+        assert_eq!(reduce(100.0, Harmonic::First), 100.0);
+        assert_eq!(reduce(50.0, Harmonic::Eighth), 5.0);
+        assert_eq!(reduce(-10.0, Harmonic::Fourth), 80.0);
+    }
+
+    #[test]
+    fn test_all_midpoints() {
+        let points = [point(Body::Sun, 10.0), point(Body::Moon, 50.0), point(Body::Mars, 200.0)];
+        let midpoints = all_midpoints(&points);
+        assert_eq!(midpoints.len(), 6);
+
+        let sun_moon = midpoints[0];
+        assert_eq!(sun_moon.label(), "SU/MO");
+        assert_eq!(sun_moon.kind, MidpointKind::Near);
+        assert_eq!(sun_moon.longitude, 30.0);
+        assert_eq!(midpoints[1].kind, MidpointKind::Far);
+        assert_eq!(midpoints[1].longitude, 210.0);
+    }
+
+    #[test]
+    fn test_sorted_list_on_90_degree_dial() {
+        let points = [
+            point(Body::Sun, 10.0),
+            point(Body::Moon, 50.0),
+            point(Body::Mars, 200.0),
+            point(Body::Saturn, 275.0),
+        ];
+        let list = MidpointList::new(&points, Harmonic::Fourth);
+
+        // Near and far midpoints coincide on the 90° dial
+        assert_eq!(list.len(), 6);
+        let positions: Vec<f64> = list.entries().iter().map(|e| e.position).collect();
+        assert!(positions.windows(2).all(|w| w[0] <= w[1]));
+        assert!(positions.iter().all(|p| (0.0..90.0).contains(p)));
+
+        // SU/MO at 30°, MA/SA at 237.5° = 57.5° on the dial
+        assert!(list.entries().iter().any(|e| e.midpoint.label() == "SU/MO" && e.position == 30.0));
+        assert!(list.entries().iter().any(|e| e.midpoint.label() == "MA/SA" && e.position == 57.5));
+    }
+
+    #[test]
+    fn test_far_midpoints_on_360_degree_dial() {
+        let points = [point(Body::Sun, 10.0), point(Body::Moon, 50.0)];
+        let list = MidpointList::new(&points, Harmonic::First);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.entries()[0].position, 30.0);
+        assert_eq!(list.entries()[1].position, 210.0);
+        assert_eq!(list.entries()[1].midpoint.kind, MidpointKind::Far);
+    }
+
+    #[test]
+    fn test_near_lookup_wraps_around() {
+        let points = [point(Body::Sun, 0.0), point(Body::Moon, 178.0), point(Body::Venus, 60.0)];
+        let list = MidpointList::new(&points, Harmonic::Fourth);
+        // SU/MO falls at 89° on the 90° dial, one degree from 0°
+        let found = list.near(0.0, 1.5);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].midpoint.label(), "SU/MO");
+    }
+
+    #[test]
+    fn test_many_points() {
+        let points: Vec<ChartPoint> = Body::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, &body)| point(body, i as f64 * 17.3))
+            .collect();
+        let list = MidpointList::new(&points, Harmonic::Fourth);
+        assert_eq!(list.len(), points.len() * (points.len() - 1) / 2);
+    }
+
+    #[test]
+    fn test_tree_on_90_degree_dial() {
+        let points = [
+            point(Body::Sun, 100.0),
+            point(Body::Moon, 80.0),
+            point(Body::Mars, 120.5),
+            point(Body::Jupiter, 0.0),
+            point(Body::Saturn, 20.0),
+            point(Body::Kronos, 5.0),
+        ];
+        let tree = MidpointTree::new(&points, PointId::Body(Body::Sun), Harmonic::Fourth, 1.0).unwrap();
+        let labels: Vec<String> = tree.entries().iter().map(|e| e.midpoint.label()).collect();
+        // JU/SA at 10° squares SU, MO/MA at 100°15' is conjunct
+        assert_eq!(labels, ["JU/SA", "MO/MA"]);
+
+        let square = tree.entries()[0];
+        assert_eq!(square.aspect, 270.0);
+        assert!(!square.direct);
+        let conjunction = tree.entries()[1];
+        assert!(conjunction.direct);
+        assert!((conjunction.orb - 0.25).abs() < 1e-9);
+        assert!(tree.entries().iter().all(|e| !e.midpoint.involves(tree.focus)));
+    }
+
+    #[test]
+    fn test_tree_opposition_is_direct() {
+        // MO/VE at 280° opposes the Sun, which the 360° dial reads via the far midpoint
+        let points = [point(Body::Sun, 100.0), point(Body::Moon, 260.0), point(Body::Venus, 300.0)];
+        let tree = MidpointTree::new(&points, PointId::Body(Body::Sun), Harmonic::First, 1.0).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.entries()[0].aspect, 180.0);
+        assert!(tree.entries()[0].direct);
+    }
+
+    #[test]
+    fn test_tree_semisquare_on_45_degree_dial() {
+        let points = [point(Body::Sun, 0.0), point(Body::Moon, 40.0), point(Body::Venus, 50.5)];
+        let tree = MidpointTree::new(&points, PointId::Body(Body::Sun), Harmonic::Eighth, 1.0).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.entries()[0].aspect, 45.0);
+        assert!(!tree.entries()[0].direct);
+        assert!((tree.entries()[0].orb - 0.25).abs() < 1e-9);
+
+        assert!(MidpointTree::new(&points, PointId::Body(Body::Pluto), Harmonic::Eighth, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_tree_of_outer_point() {
+        // A transiting Sun at 45° sits on the natal SU/MO midpoint, which the
+        // natal Sun's own tree would leave out
+        let points = [point(Body::Sun, 10.0), point(Body::Moon, 80.0), point(Body::Mars, 200.0)];
+        let sun = PointId::Body(Body::Sun);
+        let tree = MidpointTree::of_outer_point(&points, sun, 45.0, Harmonic::First, 1.0, false);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.entries()[0].midpoint.label(), "SU/MO");
+        assert!(tree.entries()[0].orb < 1e-9);
+        assert!(MidpointTree::new(&points, sun, Harmonic::First, 1.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tree_with_antiscia() {
+        // MO/VE at 110° lies on the antiscion of the Sun at 70°
+        let points = [point(Body::Sun, 70.0), point(Body::Moon, 100.0), point(Body::Venus, 120.4)];
+        let sun = PointId::Body(Body::Sun);
+        assert!(MidpointTree::new(&points, sun, Harmonic::First, 1.0).unwrap().is_empty());
+
+        let tree = MidpointTree::with_antiscia(&points, sun, Harmonic::First, 1.0).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.entries()[0].mirror, Some(Mirror::Antiscion));
+        assert!(tree.entries()[0].direct);
+        assert!((tree.entries()[0].orb - 0.2).abs() < 1e-9);
+
+        // A point on the solstice axis is its own antiscion
+        let points = [point(Body::Sun, 90.0), point(Body::Moon, 100.0), point(Body::Venus, 80.0)];
+        let tree = MidpointTree::with_antiscia(&points, sun, Harmonic::Fourth, 1.0).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.entries()[0].mirror, None);
+    }
+}
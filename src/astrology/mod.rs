@@ -1,7 +1,25 @@
 pub mod angle;
+pub mod antiscia;
+pub mod aspects;
+pub mod bodies;
+pub mod chart;
+pub mod graphic_ephemeris;
 pub mod harmonics;
+pub mod hits;
+pub mod houses;
+pub mod midpoints;
+pub mod pictures;
+pub mod progressions;
+pub mod signs;
+pub mod solar_arc;
 pub mod constants;
+pub mod ephemeris;
+pub mod time;
+pub mod transits;
+pub mod transneptunians;
 
 // Re-export commonly used types
 pub use angle::DegreePosition;
+pub use bodies::Body;
+pub use chart::{Chart, PointId};
 pub use harmonics::Harmonic;
\ No newline at end of file
@@ -0,0 +1,371 @@
+//! Planetary pictures of the Hamburg School: formulas such as `SU+MO-AS`
+//! and the search for complete pictures A+B-C=D in a chart.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::astrology::chart::{Chart, ChartPoint, PointId};
+use crate::astrology::{DegreePosition, Harmonic};
+
+/// Whether a term is added to or subtracted from the formula
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sign {
+    Plus,
+    Minus,
+}
+
+/// A signed point in a formula
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Term {
+    pub sign: Sign,
+    pub point: PointId,
+}
+
+/// Errors from parsing or evaluating a formula
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormulaError {
+    Empty,
+    UnknownPoint(String),
+    /// An operator was not followed by a point
+    MissingTerm,
+    /// The chart has no position for a point, e.g. the Ascendant at the poles
+    MissingPoint(PointId),
+}
+
+impl fmt::Display for FormulaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty formula"),
+            Self::UnknownPoint(name) => write!(f, "Unknown point \"{}\"", name),
+            Self::MissingTerm => write!(f, "Expected a point after the operator"),
+            Self::MissingPoint(point) => write!(f, "The chart has no {}", point.name()),
+        }
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+/// A sum of signed points such as `SU+MO-AS`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Formula {
+    terms: Vec<Term>,
+}
+
+impl Formula {
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self { terms }
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Evaluates the formula against a chart, returning the sensitive point
+    pub fn evaluate(&self, chart: &Chart) -> Result<DegreePosition, FormulaError> {
+        self.terms.iter().try_fold(DegreePosition::new(0, 0, 0), |sum, term| {
+            let position = chart
+                .position(term.point)
+                .ok_or(FormulaError::MissingPoint(term.point))?;
+            Ok(match term.sign {
+                Sign::Plus => sum.add(position),
+                Sign::Minus => sum.subtract(position),
+            })
+        })
+    }
+
+    /// Evaluates the formula in full precision, returning the longitude in degrees
+    pub fn longitude(&self, points: &[ChartPoint]) -> Result<f64, FormulaError> {
+        let mut sum = 0.0;
+        for term in &self.terms {
+            let point = points
+                .iter()
+                .find(|point| point.id == term.point)
+                .ok_or(FormulaError::MissingPoint(term.point))?;
+            match term.sign {
+                Sign::Plus => sum += point.longitude,
+                Sign::Minus => sum -= point.longitude,
+            }
+        }
+        Ok(sum.rem_euclid(360.0))
+    }
+}
+
+impl FromStr for Formula {
+    type Err = FormulaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut terms = Vec::new();
+        let mut sign = Sign::Plus;
+        let mut pending = false;
+        let mut name = String::new();
+
+        let mut finish = |name: &mut String, sign: Sign| -> Result<(), FormulaError> {
+            let point = PointId::from_abbreviation(name)
+                .ok_or_else(|| FormulaError::UnknownPoint(name.clone()))?;
+            terms.push(Term { sign, point });
+            name.clear();
+            Ok(())
+        };
+
+        for c in s.chars().filter(|c| !c.is_whitespace()) {
+            match c {
+                '+' | '-' => {
+                    if !name.is_empty() {
+                        finish(&mut name, sign)?;
+                    } else if pending {
+                        return Err(FormulaError::MissingTerm);
+                    }
+                    sign = if c == '+' { Sign::Plus } else { Sign::Minus };
+                    pending = true;
+                }
+                c => {
+                    name.push(c);
+                    pending = false;
+                }
+            }
+        }
+
+        if !name.is_empty() {
+            finish(&mut name, sign)?;
+        } else if pending {
+            return Err(FormulaError::MissingTerm);
+        }
+        if terms.is_empty() {
+            return Err(FormulaError::Empty);
+        }
+        Ok(Self { terms })
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            match term.sign {
+                Sign::Minus => write!(f, "-")?,
+                Sign::Plus if i > 0 => write!(f, "+")?,
+                Sign::Plus => {}
+            }
+            write!(f, "{}", term.point.abbreviation())?;
+        }
+        Ok(())
+    }
+}
+
+/// A complete planetary picture A+B-C=D
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetaryPicture {
+    /// The sensitive point formula A+B-C
+    pub formula: Formula,
+    /// The point D standing on the sensitive point
+    pub result: PointId,
+    /// Longitude of the sensitive point in degrees
+    pub sensitive_point: f64,
+    /// Distance of D from the sensitive point on the dial, in degrees
+    pub orb: f64,
+}
+
+impl PlanetaryPicture {
+    /// Whether the point appears anywhere in the picture
+    pub fn involves(&self, point: PointId) -> bool {
+        self.result == point || self.formula.terms().iter().any(|term| term.point == point)
+    }
+}
+
+impl fmt::Display for PlanetaryPicture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.formula, self.result.abbreviation())
+    }
+}
+
+/// All complete pictures of a chart within an orb on a harmonic dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct PictureList {
+    pub harmonic: Harmonic,
+    pub orb: f64,
+    pictures: Vec<PlanetaryPicture>,
+}
+
+impl PictureList {
+    /// Finds every picture A+B-C=D of four different points, sorted by orb.
+    ///
+    /// A+B-C=D is the same picture as A+B=C+D, so each one is listed once,
+    /// with A and B the earlier pair in chart order.
+    pub fn search(points: &[ChartPoint], harmonic: Harmonic, orb: f64) -> Self {
+        let span = 360.0 / harmonic.to_number() as f64;
+        let pairs: Vec<(usize, usize)> = (0..points.len())
+            .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
+            .collect();
+
+        let mut pictures = Vec::new();
+        for (n, &(a, b)) in pairs.iter().enumerate() {
+            for &(c, d) in &pairs[n + 1..] {
+                if c == a || c == b || d == a || d == b {
+                    continue;
+                }
+                let sensitive_point =
+                    (points[a].longitude + points[b].longitude - points[c].longitude).rem_euclid(360.0);
+                let arc = (sensitive_point - points[d].longitude).rem_euclid(span);
+                let distance = arc.min(span - arc);
+                if distance > orb {
+                    continue;
+                }
+                pictures.push(PlanetaryPicture {
+                    formula: Formula::new(vec![
+                        Term { sign: Sign::Plus, point: points[a].id },
+                        Term { sign: Sign::Plus, point: points[b].id },
+                        Term { sign: Sign::Minus, point: points[c].id },
+                    ]),
+                    result: points[d].id,
+                    sensitive_point,
+                    orb: distance,
+                });
+            }
+        }
+
+        pictures.sort_by(|a, b| a.orb.partial_cmp(&b.orb).unwrap_or(Ordering::Equal));
+        Self {
+            harmonic,
+            orb,
+            pictures,
+        }
+    }
+
+    /// All pictures, closest first
+    pub fn pictures(&self) -> &[PlanetaryPicture] {
+        &self.pictures
+    }
+
+    pub fn len(&self) -> usize {
+        self.pictures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pictures.is_empty()
+    }
+
+    /// Pictures that contain every one of the given points
+    pub fn involving(&self, points: &[PointId]) -> impl Iterator<Item = &PlanetaryPicture> {
+        let points = points.to_vec();
+        self.pictures
+            .iter()
+            .filter(move |picture| points.iter().all(|&point| picture.involves(point)))
+    }
+
+    /// Pictures no further than an orb from exact
+    pub fn within(&self, orb: f64) -> impl Iterator<Item = &PlanetaryPicture> {
+        self.pictures.iter().filter(move |picture| picture.orb <= orb)
+    }
+}
+
+/// Writes pictures as CSV with a header row, longitudes and orbs in degrees
+pub fn to_csv<'a>(pictures: impl IntoIterator<Item = &'a PlanetaryPicture>) -> String {
+    let mut csv = String::from("picture,sensitive_point,position,orb\n");
+    for picture in pictures {
+        csv.push_str(&format!(
+            "{},{:.4},{},{:.4}\n",
+            picture,
+            picture.sensitive_point,
+            DegreePosition::from_degrees(picture.sensitive_point).format(),
+            picture.orb,
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::BirthData;
+    use crate::astrology::Body;
+    use chrono::{FixedOffset, TimeZone};
+
+    fn point(id: PointId, longitude: f64) -> ChartPoint {
+        ChartPoint {
+            id,
+            longitude,
+            latitude: 0.0,
+            speed: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let formula: Formula = "SU+MO-AS".parse().unwrap();
+        assert_eq!(formula.terms().len(), 3);
+        assert_eq!(formula.terms()[2], Term { sign: Sign::Minus, point: PointId::Ascendant });
+        assert_eq!(formula.to_string(), "SU+MO-AS");
+
+        let spaced: Formula = " ju + sa - kr ".parse().unwrap();
+        assert_eq!(spaced.to_string(), "JU+SA-KR");
+        assert_eq!("-MC".parse::<Formula>().unwrap().to_string(), "-MC");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("".parse::<Formula>(), Err(FormulaError::Empty));
+        assert_eq!("SU+XX".parse::<Formula>(), Err(FormulaError::UnknownPoint("XX".to_string())));
+        assert_eq!("SU+".parse::<Formula>(), Err(FormulaError::MissingTerm));
+        assert_eq!("SU+-MO".parse::<Formula>(), Err(FormulaError::MissingTerm));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let datetime = offset.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        let chart = Chart::new("Sample", BirthData::new(datetime, 51.5, 0.0).unwrap());
+
+        let formula: Formula = "SU+MO-AS".parse().unwrap();
+        let expected = formula.longitude(chart.points()).unwrap();
+        let position = formula.evaluate(&chart).unwrap();
+        let diff = (position.to_degrees() - expected).rem_euclid(360.0);
+        assert!(diff.min(360.0 - diff) < 0.01);
+
+        let polar = BirthData::new(datetime, 90.0, 0.0).unwrap();
+        let polar_chart = Chart::new("Pole", polar);
+        assert_eq!(
+            formula.evaluate(&polar_chart),
+            Err(FormulaError::MissingPoint(PointId::Ascendant))
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let points = [
+            point(PointId::Body(Body::Sun), 10.0),
+            point(PointId::Body(Body::Moon), 50.0),
+            point(PointId::Body(Body::Mars), 35.0),
+            point(PointId::Midheaven, 115.5),
+        ];
+        // SU+MO-MA = 25°, which MC at 115°30' squares: a picture on the 90° dial only
+        let list = PictureList::search(&points, Harmonic::Fourth, 1.0);
+        assert_eq!(list.len(), 1);
+        let picture = &list.pictures()[0];
+        assert_eq!(picture.to_string(), "SU+MO-MA=MC");
+        assert_eq!(picture.sensitive_point, 25.0);
+        assert!((picture.orb - 0.5).abs() < 1e-9);
+
+        assert!(PictureList::search(&points, Harmonic::First, 1.0).is_empty());
+        assert_eq!(PictureList::search(&points, Harmonic::Fourth, 0.25).len(), 0);
+    }
+
+    #[test]
+    fn test_filter_and_export() {
+        let points: Vec<ChartPoint> = Body::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, &body)| point(PointId::Body(body), (i * i) as f64 * 7.3))
+            .collect();
+        let list = PictureList::search(&points, Harmonic::Fourth, 1.0);
+        assert!(!list.is_empty());
+        assert!(list.pictures().windows(2).all(|w| w[0].orb <= w[1].orb));
+
+        let sun = [PointId::Body(Body::Sun)];
+        assert!(list.involving(&sun).all(|picture| picture.involves(sun[0])));
+        assert!(list.within(0.5).all(|picture| picture.orb <= 0.5));
+
+        let csv = to_csv(list.involving(&sun));
+        assert!(csv.starts_with("picture,sensitive_point,position,orb\n"));
+        assert_eq!(csv.lines().count(), list.involving(&sun).count() + 1);
+    }
+}
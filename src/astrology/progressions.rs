@@ -0,0 +1,222 @@
+//! Progressions: the sky some days after birth standing for a later date in
+//! life, at a fixed ratio of ephemeris time to lived time.
+//!
+//! Bodies and the Node are taken from the ephemeris at the progressed
+//! moment. The angles move on by the progressed Sun's arc, as is usual for
+//! Uranian work, rather than turning with the Earth.
+
+use crate::astrology::chart::{BirthData, Chart, ChartPoint, PointId};
+use crate::astrology::solar_arc::TROPICAL_YEAR;
+use crate::astrology::{ephemeris, time, Body};
+
+/// Days in a sidereal month, the Moon's return to the same longitude
+pub const SIDEREAL_MONTH: f64 = 27.321_661;
+
+/// How much ephemeris time stands for a year of life
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ProgressionKey {
+    /// A day for a year
+    #[default]
+    Secondary,
+    /// A day for a month
+    Tertiary,
+    /// A month for a year
+    Minor,
+}
+
+impl ProgressionKey {
+    pub const ALL: [ProgressionKey; 3] = [ProgressionKey::Secondary, ProgressionKey::Tertiary, ProgressionKey::Minor];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Secondary => "Secondary",
+            Self::Tertiary => "Tertiary",
+            Self::Minor => "Minor",
+        }
+    }
+
+    /// Days of ephemeris per tropical year of life
+    pub fn days_per_year(&self) -> f64 {
+        match self {
+            Self::Secondary => 1.0,
+            Self::Tertiary => TROPICAL_YEAR / SIDEREAL_MONTH,
+            Self::Minor => SIDEREAL_MONTH,
+        }
+    }
+}
+
+/// A progression key, counted forwards from birth or backwards for the converse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Progression {
+    pub key: ProgressionKey,
+    pub converse: bool,
+}
+
+impl Progression {
+    pub fn new(key: ProgressionKey, converse: bool) -> Self {
+        Self { key, converse }
+    }
+
+    /// Returns the name, e.g. "Converse secondary"
+    pub fn name(&self) -> String {
+        if self.converse {
+            format!("Converse {}", self.key.name().to_lowercase())
+        } else {
+            self.key.name().to_string()
+        }
+    }
+
+    /// Julian Day of the sky standing for a Julian Day of life
+    pub fn progressed_jd(&self, birth: &BirthData, jd: f64) -> f64 {
+        let days = (jd - birth.julian_day()) / TROPICAL_YEAR * self.key.days_per_year();
+        if self.converse {
+            birth.julian_day() - days
+        } else {
+            birth.julian_day() + days
+        }
+    }
+
+    /// Ephemeris days passed for each day of life, negative for the converse
+    fn rate(&self) -> f64 {
+        let rate = self.key.days_per_year() / TROPICAL_YEAR;
+        if self.converse {
+            -rate
+        } else {
+            rate
+        }
+    }
+}
+
+/// Returns the progressed positions of a chart's points for a Julian Day of life.
+///
+/// Speeds are per day of life, so they show how fast the progressed points move
+/// on the dial as the date advances.
+pub fn progressed_chart(chart: &Chart, jd: f64, progression: Progression) -> Chart {
+    let progressed_jd = progression.progressed_jd(&chart.birth, jd);
+    let jde = time::julian_ephemeris_day(progressed_jd);
+    let rate = progression.rate();
+
+    let natal_sun = ephemeris::calculate(Body::Sun, time::julian_ephemeris_day(chart.birth.julian_day()));
+    let sun = ephemeris::calculate(Body::Sun, jde);
+    let arc = sun.longitude - natal_sun.longitude;
+
+    let points = chart
+        .points()
+        .iter()
+        .map(|point| match point.id {
+            PointId::Body(body) => {
+                let position = ephemeris::calculate(body, jde);
+                ChartPoint {
+                    id: point.id,
+                    longitude: position.longitude,
+                    latitude: position.latitude,
+                    speed: position.speed * rate,
+                }
+            }
+            PointId::Node => {
+                let motion = ephemeris::mean_node(jde + 0.5) - ephemeris::mean_node(jde - 0.5);
+                ChartPoint {
+                    id: point.id,
+                    longitude: ephemeris::mean_node(jde),
+                    latitude: 0.0,
+                    speed: ((motion + 180.0).rem_euclid(360.0) - 180.0) * rate,
+                }
+            }
+            PointId::Midheaven | PointId::Ascendant => ChartPoint {
+                id: point.id,
+                longitude: (point.longitude + arc).rem_euclid(360.0),
+                latitude: point.latitude,
+                speed: sun.speed * rate,
+            },
+            PointId::AriesPoint => *point,
+        })
+        .collect();
+
+    let mut birth = chart.birth.clone();
+    if let Some(datetime) = time::to_datetime(jd) {
+        birth.datetime = datetime.with_timezone(&birth.timezone());
+    }
+    Chart::from_points(format!("{} ({})", chart.name, progression.name()), birth, points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    fn chart_born(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Chart {
+        let offset = FixedOffset::east_opt(0).unwrap();
+        let datetime = offset.with_ymd_and_hms(year, month, day, hour, min, sec).unwrap();
+        Chart::new("Sample", BirthData::new(datetime, 53.55, 10.0).unwrap())
+    }
+
+    fn moon(chart: &Chart) -> f64 {
+        chart.point(PointId::Body(Body::Moon)).unwrap().longitude
+    }
+
+    #[test]
+    fn test_moon_at_progressed_date() {
+        // Each key, forwards and converse, is set up to progress to 1992 April
+        // 12 0h TT, when Meeus (Astronomical Algorithms, example 47.a) has the
+        // apparent Moon at 133.167265°. Births are ΔT = 59 s before midnight UT.
+        // This checks the progressed date and the chart built for it; it is not
+        // a comparison with progressions from other astrology software.
+        let cases = [
+            (Progression::new(ProgressionKey::Secondary, false), (1992, 4, 1), 10.0 * TROPICAL_YEAR),
+            (Progression::new(ProgressionKey::Secondary, true), (1992, 4, 21), 10.0 * TROPICAL_YEAR),
+            (Progression::new(ProgressionKey::Tertiary, false), (1992, 4, 1), 10.0 * SIDEREAL_MONTH),
+            (Progression::new(ProgressionKey::Tertiary, true), (1992, 4, 21), 10.0 * SIDEREAL_MONTH),
+        ];
+        for (progression, (year, month, day), age) in cases {
+            let chart = chart_born(year, month, day, 23, 59, 1);
+            let progressed = progressed_chart(&chart, chart.birth.julian_day() + age, progression);
+            let moon = moon(&progressed);
+            assert!((moon - 133.167_265).abs() < 0.01, "{} moon {}", progression.name(), moon);
+        }
+        let chart = chart_born(1992, 4, 1, 23, 59, 1);
+        let progressed = progressed_chart(&chart, chart.birth.julian_day(), Progression::default());
+        assert_eq!(progressed.name, "Sample (Secondary)");
+    }
+
+    #[test]
+    fn test_keys() {
+        let chart = chart_born(1980, 5, 17, 14, 30, 0);
+        let birth = chart.birth.julian_day();
+        let jd = birth + 2.0 * TROPICAL_YEAR;
+
+        let secondary = Progression::new(ProgressionKey::Secondary, false);
+        assert!((secondary.progressed_jd(&chart.birth, jd) - (birth + 2.0)).abs() < 1e-9);
+
+        // A month of life is a day after birth, so two years are some 26.7 days
+        let tertiary = Progression::new(ProgressionKey::Tertiary, false);
+        assert!((tertiary.progressed_jd(&chart.birth, jd) - birth - 26.736).abs() < 0.001);
+
+        // Two years of minor progression bring the Moon back round twice
+        let minor = Progression::new(ProgressionKey::Minor, false);
+        assert!((minor.progressed_jd(&chart.birth, jd) - (birth + 2.0 * SIDEREAL_MONTH)).abs() < 1e-9);
+        let progressed = progressed_chart(&chart, jd, minor);
+        let diff = (moon(&progressed) - moon(&chart) + 180.0).rem_euclid(360.0) - 180.0;
+        assert!(diff.abs() < 3.0, "diff {}", diff);
+    }
+
+    #[test]
+    fn test_converse() {
+        let chart = chart_born(1980, 5, 17, 14, 30, 0);
+        let birth = chart.birth.julian_day();
+        let jd = birth + 5.0 * TROPICAL_YEAR;
+        let converse = Progression::new(ProgressionKey::Secondary, true);
+        assert!((converse.progressed_jd(&chart.birth, jd) - (birth - 5.0)).abs() < 1e-9);
+        assert_eq!(converse.name(), "Converse secondary");
+
+        // The converse Sun and angles fall back by about five degrees
+        let progressed = progressed_chart(&chart, jd, converse);
+        let sun = PointId::Body(Body::Sun);
+        let arc = progressed.point(sun).unwrap().longitude - chart.point(sun).unwrap().longitude;
+        assert!(arc < -4.5 && arc > -5.5, "arc {}", arc);
+        let mc = progressed.point(PointId::Midheaven).unwrap().longitude;
+        let natal_mc = chart.point(PointId::Midheaven).unwrap().longitude;
+        assert!(((mc - natal_mc).rem_euclid(360.0) - 360.0 - arc).abs() < 1e-9);
+        assert!(progressed.point(sun).unwrap().speed < 0.0);
+        assert_eq!(progressed.position(PointId::AriesPoint), chart.position(PointId::AriesPoint));
+    }
+}
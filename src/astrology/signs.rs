@@ -0,0 +1,522 @@
+//! The twelve signs of the tropical zodiac, and writing positions in them.
+//!
+//! A position can be written absolutely, "123°45'", within its sign as
+//! "3°45' Leo", "3Le45" or "3♌45", or on the 90° dial within its modality,
+//! "3°45' fixed". The parser reads all of these back, as well as decimal degrees.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::astrology::angle::ARCSECONDS_PER_CIRCLE;
+use crate::astrology::DegreePosition;
+
+/// Arcseconds in a sign, which is also a third of the 90° dial
+const ARCSECONDS_PER_SIGN: u32 = ARCSECONDS_PER_CIRCLE / 12;
+
+/// A zodiac sign, 30° of ecliptic longitude starting at 0° Aries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZodiacSign {
+    Aries,
+    Taurus,
+    Gemini,
+    Cancer,
+    Leo,
+    Virgo,
+    Libra,
+    Scorpio,
+    Sagittarius,
+    Capricorn,
+    Aquarius,
+    Pisces,
+}
+
+impl ZodiacSign {
+    /// Every sign in zodiacal order
+    pub const ALL: [ZodiacSign; 12] = [
+        Self::Aries,
+        Self::Taurus,
+        Self::Gemini,
+        Self::Cancer,
+        Self::Leo,
+        Self::Virgo,
+        Self::Libra,
+        Self::Scorpio,
+        Self::Sagittarius,
+        Self::Capricorn,
+        Self::Aquarius,
+        Self::Pisces,
+    ];
+
+    /// Returns the sign containing a longitude in degrees
+    pub fn of(longitude: f64) -> Self {
+        let index = (longitude.rem_euclid(360.0) / 30.0) as usize;
+        Self::ALL[index.min(11)]
+    }
+
+    /// Longitude of the first degree of the sign
+    pub fn start(&self) -> f64 {
+        self.index() as f64 * 30.0
+    }
+
+    /// Place of the sign in zodiacal order, Aries being 0
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|sign| sign == self).unwrap_or(0)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Aries => "Aries",
+            Self::Taurus => "Taurus",
+            Self::Gemini => "Gemini",
+            Self::Cancer => "Cancer",
+            Self::Leo => "Leo",
+            Self::Virgo => "Virgo",
+            Self::Libra => "Libra",
+            Self::Scorpio => "Scorpio",
+            Self::Sagittarius => "Sagittarius",
+            Self::Capricorn => "Capricorn",
+            Self::Aquarius => "Aquarius",
+            Self::Pisces => "Pisces",
+        }
+    }
+
+    /// Two-letter abbreviation, e.g. "Le" for Leo
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Aries => "Ar",
+            Self::Taurus => "Ta",
+            Self::Gemini => "Ge",
+            Self::Cancer => "Cn",
+            Self::Leo => "Le",
+            Self::Virgo => "Vi",
+            Self::Libra => "Li",
+            Self::Scorpio => "Sc",
+            Self::Sagittarius => "Sg",
+            Self::Capricorn => "Cp",
+            Self::Aquarius => "Aq",
+            Self::Pisces => "Pi",
+        }
+    }
+
+    /// Looks a sign up by its name, abbreviation, first three letters or glyph,
+    /// ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sign| {
+            let full = sign.name();
+            name.eq_ignore_ascii_case(full)
+                || name.eq_ignore_ascii_case(sign.abbreviation())
+                || (name.len() == 3 && name.eq_ignore_ascii_case(&full[..3]))
+                || name == sign.glyph()
+        })
+    }
+
+    /// The signs run cardinal, fixed, mutable from Aries onwards
+    pub fn modality(&self) -> Modality {
+        Modality::ALL[self.index() % 3]
+    }
+
+    /// Returns the Unicode symbol of the sign, e.g. ♌ for Leo
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Self::Aries => "\u{2648}",
+            Self::Taurus => "\u{2649}",
+            Self::Gemini => "\u{264A}",
+            Self::Cancer => "\u{264B}",
+            Self::Leo => "\u{264C}",
+            Self::Virgo => "\u{264D}",
+            Self::Libra => "\u{264E}",
+            Self::Scorpio => "\u{264F}",
+            Self::Sagittarius => "\u{2650}",
+            Self::Capricorn => "\u{2651}",
+            Self::Aquarius => "\u{2652}",
+            Self::Pisces => "\u{2653}",
+        }
+    }
+}
+
+/// The cardinal, fixed and mutable signs, each a third of the 90° dial
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modality {
+    Cardinal,
+    Fixed,
+    Mutable,
+}
+
+impl Modality {
+    pub const ALL: [Modality; 3] = [Self::Cardinal, Self::Fixed, Self::Mutable];
+
+    /// Returns the modality of the sign containing a longitude
+    pub fn of(longitude: f64) -> Self {
+        ZodiacSign::of(longitude).modality()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cardinal => "cardinal",
+            Self::Fixed => "fixed",
+            Self::Mutable => "mutable",
+        }
+    }
+
+    /// Looks a modality up by its name or first three letters, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|modality| {
+            let full = modality.name();
+            name.eq_ignore_ascii_case(full) || (name.len() == 3 && name.eq_ignore_ascii_case(&full[..3]))
+        })
+    }
+
+    /// Where the modality's third of the 90° dial begins, which is where its
+    /// first sign from Aries begins
+    pub fn dial_start(&self) -> f64 {
+        let first = Self::ALL.iter().position(|modality| modality == self).unwrap_or(0);
+        ZodiacSign::ALL[first].start()
+    }
+}
+
+/// How a position is written relative to the signs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignNotation {
+    /// "3°45' Leo"
+    Name,
+    /// "3Le45"
+    Abbreviation,
+    /// "3♌45"
+    Glyph,
+    /// The place on the 90° dial within its modality, "3°45' fixed"
+    Modality,
+}
+
+impl DegreePosition {
+    /// Returns the sign the position is in
+    pub fn sign(&self) -> ZodiacSign {
+        ZodiacSign::ALL[(self.arcseconds() / ARCSECONDS_PER_SIGN) as usize]
+    }
+
+    /// Returns the position from the start of its sign
+    pub fn within_sign(&self) -> DegreePosition {
+        DegreePosition::from_arcseconds((self.arcseconds() % ARCSECONDS_PER_SIGN) as i64)
+    }
+
+    /// Formats the position in its sign, or on the 90° dial in its modality
+    pub fn format_sign(&self, notation: SignNotation) -> String {
+        let within = self.within_sign();
+        let compact = |mark: &str| {
+            let mut text = format!("{}{}{:02}", within.degrees(), mark, within.minutes());
+            if within.seconds() != 0 {
+                text.push_str(&format!("'{:02}\"", within.seconds()));
+            }
+            text
+        };
+        match notation {
+            SignNotation::Name => format!("{} {}", within.format(), self.sign().name()),
+            SignNotation::Abbreviation => compact(self.sign().abbreviation()),
+            SignNotation::Glyph => compact(self.sign().glyph()),
+            SignNotation::Modality => format!("{} {}", within.format(), self.sign().modality().name()),
+        }
+    }
+}
+
+/// Formats a longitude within its sign, e.g. "10°22'02\" Capricorn"
+pub fn format_in_sign(longitude: f64) -> String {
+    DegreePosition::from_degrees(longitude).format_sign(SignNotation::Name)
+}
+
+/// Why a position could not be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsePositionError {
+    Empty,
+    UnexpectedCharacter(char),
+    InvalidNumber(String),
+    /// A word that is neither a sign nor a modality
+    UnknownSign(String),
+    /// More than one sign or modality, e.g. "3 Leo Virgo"
+    ExtraSign(String),
+    /// A sign but no degrees
+    MissingDegrees,
+    /// Parts out of order or repeated, e.g. "45' 3°", or more than three numbers
+    Misordered,
+    /// Only the last number may have a decimal part, e.g. "3.5°45'"
+    FractionNotLast,
+    /// Only absolute degrees can be negative
+    NegativeInSign,
+    /// A part too large for its unit, e.g. 75 minutes
+    OutOfRange { part: &'static str, value: f64, limit: u32 },
+}
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty position"),
+            Self::UnexpectedCharacter(c) => write!(f, "Unexpected character '{}'", c),
+            Self::InvalidNumber(number) => write!(f, "Invalid number \"{}\"", number),
+            Self::UnknownSign(name) => write!(f, "Unknown sign or modality \"{}\"", name),
+            Self::ExtraSign(name) => write!(f, "Only one sign or modality is allowed, found \"{}\" too", name),
+            Self::MissingDegrees => write!(f, "Expected degrees"),
+            Self::Misordered => write!(f, "Expected degrees, minutes and seconds in that order"),
+            Self::FractionNotLast => write!(f, "Only the last number may have a decimal part"),
+            Self::NegativeInSign => write!(f, "A position in a sign or modality cannot be negative"),
+            Self::OutOfRange { part, value, limit } => {
+                write!(f, "{} must be below {}, got {}", part, limit, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParsePositionError {}
+
+/// What a position is counted from
+enum Reference {
+    Sign(ZodiacSign),
+    Modality(Modality),
+}
+
+/// A number in a position and the unit marked after it, 0 to 2 for degrees to seconds
+struct Part {
+    value: f64,
+    unit: Option<usize>,
+}
+
+impl FromStr for DegreePosition {
+    type Err = ParsePositionError;
+
+    /// Parses "123.75", "123°45'12\"", "3°45' Leo", "3Le45", "3♌45" or "3°45' fixed"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<Part> = Vec::new();
+        let mut reference: Option<Reference> = None;
+        let mut negative = false;
+        let mut chars = s.trim().chars().peekable();
+        if chars.peek().is_none() {
+            return Err(ParsePositionError::Empty);
+        }
+
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || c == '\u{FE0E}' || c == '\u{FE0F}' {
+                // Text and emoji variation selectors may follow a glyph
+                chars.next();
+            } else if c == '-' && parts.is_empty() && reference.is_none() && !negative {
+                negative = true;
+                chars.next();
+            } else if c.is_ascii_digit() || c == '.' {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '.') {
+                    text.push(c);
+                    chars.next();
+                }
+                let value = text
+                    .parse::<f64>()
+                    .map_err(|_| ParsePositionError::InvalidNumber(text))?;
+                let unit = match chars.peek() {
+                    Some('°' | 'º') => Some(0),
+                    Some('\'' | '′' | '’') => {
+                        chars.next();
+                        // Two quotes mark seconds
+                        if chars.peek() == Some(&'\'') {
+                            Some(2)
+                        } else {
+                            Some(1)
+                        }
+                    }
+                    Some('"' | '″' | '”') => Some(2),
+                    _ => None,
+                };
+                if matches!(unit, Some(0) | Some(2)) {
+                    chars.next();
+                }
+                parts.push(Part { value, unit });
+            } else {
+                let word: String = if c.is_alphabetic() {
+                    let mut word = String::new();
+                    while let Some(&c) = chars.peek().filter(|c| c.is_alphabetic()) {
+                        word.push(c);
+                        chars.next();
+                    }
+                    word
+                } else {
+                    chars.next();
+                    c.to_string()
+                };
+                let found = ZodiacSign::from_name(&word)
+                    .map(Reference::Sign)
+                    .or_else(|| Modality::from_name(&word).map(Reference::Modality))
+                    .ok_or_else(|| match word.chars().next() {
+                        Some(c) if !c.is_alphabetic() => ParsePositionError::UnexpectedCharacter(c),
+                        _ => ParsePositionError::UnknownSign(word.clone()),
+                    })?;
+                if reference.is_some() {
+                    return Err(ParsePositionError::ExtraSign(word));
+                }
+                reference = Some(found);
+            }
+        }
+
+        if parts.is_empty() {
+            return Err(ParsePositionError::MissingDegrees);
+        }
+        // Unmarked numbers take the unit after the one before
+        let mut fields = [0.0; 3];
+        let mut next = 0;
+        for (i, part) in parts.iter().enumerate() {
+            let unit = part.unit.unwrap_or(next);
+            if unit < next || unit > 2 {
+                return Err(ParsePositionError::Misordered);
+            }
+            if part.value.fract() != 0.0 && i + 1 < parts.len() {
+                return Err(ParsePositionError::FractionNotLast);
+            }
+            fields[unit] = part.value;
+            next = unit + 1;
+        }
+
+        let degree_limit = reference.as_ref().map(|_| 30);
+        for (part, value, limit) in [
+            ("Degrees", fields[0], degree_limit),
+            ("Minutes", fields[1], Some(60)),
+            ("Seconds", fields[2], Some(60)),
+        ] {
+            if let Some(limit) = limit.filter(|&limit| value >= limit as f64) {
+                return Err(ParsePositionError::OutOfRange { part, value, limit });
+            }
+        }
+        let degrees = fields[0] + fields[1] / 60.0 + fields[2] / 3600.0;
+
+        Ok(DegreePosition::from_degrees(match reference {
+            None if negative => -degrees,
+            None => degrees,
+            Some(_) if negative => return Err(ParsePositionError::NegativeInSign),
+            Some(Reference::Sign(sign)) => sign.start() + degrees,
+            Some(Reference::Modality(modality)) => modality.dial_start() + degrees,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::constants::{CARDINAL_POINTS, FIXED_POINTS};
+
+    #[test]
+    fn test_sign_of() {
+        assert_eq!(ZodiacSign::of(0.0), ZodiacSign::Aries);
+        assert_eq!(ZodiacSign::of(29.999), ZodiacSign::Aries);
+        assert_eq!(ZodiacSign::of(125.0), ZodiacSign::Leo);
+        assert_eq!(ZodiacSign::of(359.99), ZodiacSign::Pisces);
+        assert_eq!(ZodiacSign::of(-10.0), ZodiacSign::Pisces);
+        assert_eq!(ZodiacSign::Leo.start(), 120.0);
+    }
+
+    #[test]
+    fn test_format_in_sign() {
+        assert_eq!(format_in_sign(280.5), "10°30' Capricorn");
+        assert_eq!(format_in_sign(123.75), "3°45' Leo");
+    }
+
+    #[test]
+    fn test_format_in_sign_rounding() {
+        // A longitude rounding up to the next sign is shown in that sign
+        assert_eq!(format_in_sign(29.9999999), "0°00' Taurus");
+        assert_eq!(format_in_sign(-0.5), "29°30' Pisces");
+    }
+
+    #[test]
+    fn test_modality() {
+        for point in CARDINAL_POINTS {
+            assert_eq!(Modality::of(point as f64), Modality::Cardinal);
+        }
+        for point in FIXED_POINTS {
+            assert_eq!(Modality::of(point as f64), Modality::Fixed);
+        }
+        for sign in ZodiacSign::ALL {
+            let modality = sign.modality();
+            assert_eq!(Modality::of(sign.start() + 29.9), modality);
+            assert_eq!(ZodiacSign::of(sign.start() - modality.dial_start()).modality(), Modality::Cardinal);
+        }
+        assert_eq!(Modality::Cardinal.dial_start(), 0.0);
+        assert_eq!(Modality::Fixed.dial_start(), 30.0);
+        assert_eq!(Modality::Mutable.dial_start(), 60.0);
+        assert_eq!(Modality::of(45.0), Modality::Fixed);
+        assert_eq!(Modality::of(75.0), Modality::Mutable);
+        assert_eq!(Modality::of(359.0), Modality::Mutable);
+        assert_eq!(ZodiacSign::Scorpio.modality(), Modality::Fixed);
+        assert_eq!(ZodiacSign::Capricorn.modality(), Modality::Cardinal);
+    }
+
+    #[test]
+    fn test_format_sign() {
+        let leo = DegreePosition::new(123, 45, 0);
+        assert_eq!(leo.sign(), ZodiacSign::Leo);
+        assert_eq!(leo.within_sign(), DegreePosition::new(3, 45, 0));
+        assert_eq!(leo.format_sign(SignNotation::Name), "3°45' Leo");
+        assert_eq!(leo.format_sign(SignNotation::Abbreviation), "3Le45");
+        assert_eq!(leo.format_sign(SignNotation::Glyph), "3\u{264C}45");
+        assert_eq!(leo.format_sign(SignNotation::Modality), "3°45' fixed");
+
+        let seconds = DegreePosition::new(359, 5, 12);
+        assert_eq!(seconds.format_sign(SignNotation::Name), "29°05'12\" Pisces");
+        assert_eq!(seconds.format_sign(SignNotation::Abbreviation), "29Pi05'12\"");
+        assert_eq!(seconds.format_sign(SignNotation::Modality), "29°05'12\" mutable");
+    }
+
+    #[test]
+    fn test_parse() {
+        let parse = |text: &str| text.parse::<DegreePosition>();
+        let expected = Ok(DegreePosition::new(123, 45, 0));
+        for text in [
+            "123.75", "123.75°", "123°45'", "123° 45'", "123 45", "3°45' Leo", "3°45'Leo", "leo 3°45'",
+            "3Le45", "3 le 45", "3LEO45", "3\u{264C}45", "3\u{264C}\u{FE0F}45", "3.75 Leo",
+        ] {
+            assert_eq!(parse(text), expected, "{}", text);
+        }
+        assert_eq!(parse("3°45'12\" Leo"), Ok(DegreePosition::new(123, 45, 12)));
+        assert_eq!(parse("3Le45'12"), Ok(DegreePosition::new(123, 45, 12)));
+        assert_eq!(parse("3°45'12'' Leo"), Ok(DegreePosition::new(123, 45, 12)));
+        assert_eq!(parse("0 Ari"), Ok(DegreePosition::new(0, 0, 0)));
+        assert_eq!(parse("29°59'59\" Pisces"), Ok(DegreePosition::new(359, 59, 59)));
+        assert_eq!(parse("-10"), Ok(DegreePosition::new(350, 0, 0)));
+        assert_eq!(parse("370"), Ok(DegreePosition::new(10, 0, 0)));
+        // On the 90° dial
+        assert_eq!(parse("3°45' fixed"), Ok(DegreePosition::new(33, 45, 0)));
+        assert_eq!(parse("15 Mut"), Ok(DegreePosition::new(75, 0, 0)));
+        assert_eq!(parse("0 cardinal"), Ok(DegreePosition::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |text: &str| text.parse::<DegreePosition>();
+        assert_eq!(parse("  "), Err(ParsePositionError::Empty));
+        assert_eq!(parse("3°45' Lea"), Err(ParsePositionError::UnknownSign("Lea".to_string())));
+        assert_eq!(parse("3 Leo Virgo"), Err(ParsePositionError::ExtraSign("Virgo".to_string())));
+        assert_eq!(parse("Leo"), Err(ParsePositionError::MissingDegrees));
+        assert_eq!(parse("1.2.3"), Err(ParsePositionError::InvalidNumber("1.2.3".to_string())));
+        assert_eq!(parse("12#"), Err(ParsePositionError::UnexpectedCharacter('#')));
+        assert_eq!(parse("45' 3°"), Err(ParsePositionError::Misordered));
+        assert_eq!(parse("1 2 3 4"), Err(ParsePositionError::Misordered));
+        assert_eq!(parse("3.5°45'"), Err(ParsePositionError::FractionNotLast));
+        assert_eq!(parse("-3 Leo"), Err(ParsePositionError::NegativeInSign));
+        assert_eq!(
+            parse("35 Leo"),
+            Err(ParsePositionError::OutOfRange { part: "Degrees", value: 35.0, limit: 30 })
+        );
+        assert_eq!(
+            parse("3°75'"),
+            Err(ParsePositionError::OutOfRange { part: "Minutes", value: 75.0, limit: 60 })
+        );
+        assert_eq!(parse("3°75'").unwrap_err().to_string(), "Minutes must be below 60, got 75");
+        assert_eq!(parse("3 Lea").unwrap_err().to_string(), "Unknown sign or modality \"Lea\"");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        // Every notation reads back to the same position, to the second
+        for step in 0..2000 {
+            let position = DegreePosition::from_arcseconds(step * 647 + 13);
+            for notation in [SignNotation::Name, SignNotation::Abbreviation, SignNotation::Glyph] {
+                let text = position.format_sign(notation);
+                assert_eq!(text.parse::<DegreePosition>(), Ok(position), "{}", text);
+            }
+            assert_eq!(position.format().parse::<DegreePosition>(), Ok(position));
+            let dial = position.format_sign(SignNotation::Modality).parse::<DegreePosition>();
+            assert_eq!(dial, Ok(DegreePosition::from_arcseconds(position.arcseconds() as i64 % (90 * 3600))));
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Solar arc directions: every point of a chart moved on by the arc the
+//! Sun has travelled since birth, a day of motion standing for a year of life.
+
+use crate::astrology::chart::{BirthData, Chart, ChartPoint, PointId};
+use crate::astrology::{ephemeris, time, Body};
+
+/// Days in a tropical year, the unit of age for directions
+pub const TROPICAL_YEAR: f64 = 365.242_19;
+
+/// Mean daily motion of the Sun, 59'08.33", used as the Naibod arc per year
+pub const NAIBOD_RATE: f64 = 0.985_647_3;
+
+/// How the arc for an age is found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ArcMethod {
+    /// The Sun's actual motion in the days after birth
+    #[default]
+    True,
+    /// The Sun's mean motion, the same for every year of life
+    Naibod,
+}
+
+impl ArcMethod {
+    pub const ALL: [ArcMethod; 2] = [ArcMethod::True, ArcMethod::Naibod];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::True => "Solar arc",
+            Self::Naibod => "Naibod arc",
+        }
+    }
+}
+
+/// Age in tropical years at a Julian Day, negative before birth
+pub fn age(birth: &BirthData, jd: f64) -> f64 {
+    (jd - birth.julian_day()) / TROPICAL_YEAR
+}
+
+/// Returns the Sun's longitude that many days after birth, one for each year of age
+fn progressed_sun(birth: &BirthData, age: f64) -> ephemeris::EclipticPosition {
+    let jd = birth.julian_day() + age;
+    ephemeris::calculate(Body::Sun, time::julian_ephemeris_day(jd))
+}
+
+/// The arc in degrees for a Julian Day in Universal Time
+pub fn solar_arc(chart: &Chart, jd: f64, method: ArcMethod) -> f64 {
+    let age = age(&chart.birth, jd);
+    match method {
+        ArcMethod::True => {
+            let natal = progressed_sun(&chart.birth, 0.0).longitude;
+            let progressed = progressed_sun(&chart.birth, age).longitude;
+            // The true arc keeps within a few degrees of the mean one, which
+            // tells how many whole turns the wrapped difference lost
+            let arc = (progressed - natal + 180.0).rem_euclid(360.0) - 180.0;
+            let turns = ((age * NAIBOD_RATE - arc) / 360.0).round();
+            arc + turns * 360.0
+        }
+        ArcMethod::Naibod => age * NAIBOD_RATE,
+    }
+}
+
+/// Returns the chart with every point moved on by the arc for a Julian Day.
+///
+/// The directed chart is dated to that day, and its points move at the
+/// rate the arc grows per day of real time.
+pub fn directed_chart(chart: &Chart, jd: f64, method: ArcMethod) -> Chart {
+    let arc = solar_arc(chart, jd, method);
+    let rate = match method {
+        ArcMethod::True => progressed_sun(&chart.birth, age(&chart.birth, jd)).speed / TROPICAL_YEAR,
+        ArcMethod::Naibod => NAIBOD_RATE / TROPICAL_YEAR,
+    };
+    let points = chart
+        .points()
+        .iter()
+        .map(|point| ChartPoint {
+            id: point.id,
+            longitude: (point.longitude + arc).rem_euclid(360.0),
+            latitude: point.latitude,
+            speed: rate,
+        })
+        .collect();
+
+    let mut birth = chart.birth.clone();
+    if let Some(datetime) = time::to_datetime(jd) {
+        birth.datetime = datetime.with_timezone(&birth.timezone());
+    }
+    Chart::from_points(format!("{} ({})", chart.name, method.name()), birth, points)
+}
+
+/// Returns where a natal point is directed to, if the chart has it
+pub fn directed_position(chart: &Chart, point: PointId, jd: f64, method: ArcMethod) -> Option<f64> {
+    let natal = chart.point(point)?.longitude;
+    Some((natal + solar_arc(chart, jd, method)).rem_euclid(360.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    fn sample_chart() -> Chart {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let datetime = offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap();
+        Chart::new("Sample", BirthData::new(datetime, 53.55, 10.0).unwrap())
+    }
+
+    #[test]
+    fn test_naibod_arc() {
+        let chart = sample_chart();
+        let jd = chart.birth.julian_day() + 30.0 * TROPICAL_YEAR;
+        assert!((solar_arc(&chart, jd, ArcMethod::Naibod) - 29.569_419).abs() < 1e-6);
+        assert_eq!(solar_arc(&chart, chart.birth.julian_day(), ArcMethod::Naibod), 0.0);
+    }
+
+    #[test]
+    fn test_true_arc() {
+        let chart = sample_chart();
+        let birth = chart.birth.julian_day();
+        assert!(solar_arc(&chart, birth, ArcMethod::True).abs() < 1e-9);
+
+        // Born in May, the Sun moves a little slower than average through the summer
+        let arc = solar_arc(&chart, birth + 30.0 * TROPICAL_YEAR, ArcMethod::True);
+        assert!(arc > 28.5 && arc < 29.5, "arc {}", arc);
+
+        // A full turn of the Sun after a lifetime of directions is not lost
+        let arc = solar_arc(&chart, birth + 400.0 * TROPICAL_YEAR, ArcMethod::True);
+        assert!(arc > 380.0 && arc < 410.0, "arc {}", arc);
+
+        // Before birth the arc runs backwards
+        assert!(solar_arc(&chart, birth - TROPICAL_YEAR, ArcMethod::True) < 0.0);
+    }
+
+    #[test]
+    fn test_directed_chart() {
+        let chart = sample_chart();
+        let jd = chart.birth.julian_day() + 10.0 * TROPICAL_YEAR;
+        let directed = directed_chart(&chart, jd, ArcMethod::Naibod);
+        assert_eq!(directed.points().len(), chart.points().len());
+        assert_eq!(directed.name, "Sample (Naibod arc)");
+        assert_eq!(directed.birth.datetime.date_naive().to_string(), "1990-05-18");
+
+        let arc = solar_arc(&chart, jd, ArcMethod::Naibod);
+        for (natal, directed) in chart.points().iter().zip(directed.points()) {
+            assert_eq!(natal.id, directed.id);
+            let moved = (directed.longitude - natal.longitude).rem_euclid(360.0);
+            assert!((moved - arc).abs() < 1e-9);
+        }
+
+        let sun = PointId::Body(Body::Sun);
+        let expected = directed.point(sun).unwrap().longitude;
+        assert_eq!(directed_position(&chart, sun, jd, ArcMethod::Naibod), Some(expected));
+    }
+}
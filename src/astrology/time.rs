@@ -0,0 +1,223 @@
+//! Time scales: Julian Day in Universal and Terrestrial Time, Delta T and
+//! sidereal time.
+//!
+//! Calendar dates are handled by `chrono`, which uses the proleptic Gregorian
+//! calendar, so dates before 1582 October 15 must be given in that calendar.
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::astrology::ephemeris::{self, J2000};
+
+/// Julian Day of the Unix epoch, 1970 January 1 0h UT
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Converts a date-time in any timezone to a Julian Day in Universal Time
+pub fn julian_day<Tz: TimeZone>(datetime: &DateTime<Tz>) -> f64 {
+    let seconds = datetime.timestamp() as f64 + datetime.timestamp_subsec_nanos() as f64 * 1e-9;
+    UNIX_EPOCH_JD + seconds / SECONDS_PER_DAY
+}
+
+/// Converts a Julian Day in Universal Time back to a UTC date-time
+pub fn to_datetime(jd_ut: f64) -> Option<DateTime<Utc>> {
+    let seconds = (jd_ut - UNIX_EPOCH_JD) * SECONDS_PER_DAY;
+    let whole = seconds.floor();
+    let nanos = ((seconds - whole) * 1e9).round().min(999_999_999.0);
+    DateTime::from_timestamp(whole as i64, nanos as u32)
+}
+
+/// Converts a Julian Day in Universal Time to a Julian Ephemeris Day (TT)
+pub fn julian_ephemeris_day(jd_ut: f64) -> f64 {
+    jd_ut + delta_t(decimal_year(jd_ut)) / SECONDS_PER_DAY
+}
+
+/// Converts a date-time to a Julian Ephemeris Day (TT)
+pub fn julian_ephemeris_day_for<Tz: TimeZone>(datetime: &DateTime<Tz>) -> f64 {
+    julian_ephemeris_day(julian_day(datetime))
+}
+
+/// Returns the year with fraction for a Julian Day, e.g. 2000.0 at J2000.0
+pub fn decimal_year(jd: f64) -> f64 {
+    2000.0 + (jd - J2000) / 365.25
+}
+
+/// Difference TT - UT in seconds for a decimal year.
+///
+/// Uses the polynomials of Espenak and Meeus (2006), fitted to historical
+/// observations from -500 to 2005 and extrapolated to 2150, with the
+/// long-term parabola of Morrison and Stephenson outside that range.
+pub fn delta_t(year: f64) -> f64 {
+    match year {
+        y if y < -500.0 => long_term(y),
+        y if y < 500.0 => {
+            let u = y / 100.0;
+            polynomial(
+                u,
+                &[10583.6, -1014.41, 33.78311, -5.952053, -0.1798452, 0.022174192, 0.0090316521],
+            )
+        }
+        y if y < 1600.0 => {
+            let u = (y - 1000.0) / 100.0;
+            polynomial(
+                u,
+                &[1574.2, -556.01, 71.23472, 0.319781, -0.8503463, -0.005050998, 0.0083572073],
+            )
+        }
+        y if y < 1700.0 => polynomial(y - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0]),
+        y if y < 1800.0 => polynomial(
+            y - 1700.0,
+            &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1_174_000.0],
+        ),
+        y if y < 1860.0 => polynomial(
+            y - 1800.0,
+            &[
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ],
+        ),
+        y if y < 1900.0 => polynomial(
+            y - 1860.0,
+            &[7.62, 0.5737, -0.251754, 0.01680668, -0.0004473624, 1.0 / 233_174.0],
+        ),
+        y if y < 1920.0 => polynomial(
+            y - 1900.0,
+            &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197],
+        ),
+        y if y < 1941.0 => polynomial(y - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936]),
+        y if y < 1961.0 => polynomial(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => polynomial(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => polynomial(
+            y - 2000.0,
+            &[63.86, 0.3345, -0.060374, 0.0017275, 0.000651814, 0.00002373599],
+        ),
+        y if y < 2050.0 => polynomial(y - 2000.0, &[62.92, 0.32217, 0.005589]),
+        y if y < 2150.0 => long_term(y) - 0.5628 * (2150.0 - y),
+        y => long_term(y),
+    }
+}
+
+fn long_term(year: f64) -> f64 {
+    let u = (year - 1820.0) / 100.0;
+    -20.0 + 32.0 * u * u
+}
+
+fn polynomial(x: f64, coefficients: &[f64]) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, c| sum * x + c)
+}
+
+/// Greenwich mean sidereal time in degrees [0, 360) for a Julian Day in UT
+pub fn greenwich_mean_sidereal_time(jd_ut: f64) -> f64 {
+    let t = (jd_ut - J2000) / 36525.0;
+    let theta = 280.46061837 + 360.98564736629 * (jd_ut - J2000) + 0.000387933 * t * t
+        - t * t * t / 38_710_000.0;
+    theta.rem_euclid(360.0)
+}
+
+/// Greenwich apparent sidereal time in degrees, corrected for nutation
+pub fn greenwich_apparent_sidereal_time(jd_ut: f64) -> f64 {
+    let jde = julian_ephemeris_day(jd_ut);
+    let nutation = ephemeris::nutation(jde);
+    let obliquity = ephemeris::mean_obliquity(jde) + nutation.obliquity;
+    let equation_of_equinoxes = nutation.longitude * obliquity.to_radians().cos();
+    (greenwich_mean_sidereal_time(jd_ut) + equation_of_equinoxes).rem_euclid(360.0)
+}
+
+/// Local apparent sidereal time in degrees for a geographic longitude (east positive)
+pub fn local_apparent_sidereal_time(jd_ut: f64, longitude: f64) -> f64 {
+    (greenwich_apparent_sidereal_time(jd_ut) + longitude).rem_euclid(360.0)
+}
+
+/// True obliquity of the ecliptic in degrees for a Julian Day in UT
+pub fn obliquity(jd_ut: f64) -> f64 {
+    ephemeris::true_obliquity(julian_ephemeris_day(jd_ut))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate};
+
+    fn hours(h: f64, m: f64, s: f64) -> f64 {
+        (h + m / 60.0 + s / 3600.0) * 15.0
+    }
+
+    #[test]
+    fn test_julian_day() {
+        // Meeus, example 7.a: 1957 Oct 4.81 = JD 2436116.31
+        let launch = Utc.with_ymd_and_hms(1957, 10, 4, 19, 26, 24).unwrap();
+        assert!((julian_day(&launch) - 2436116.31).abs() < 1e-6);
+
+        let j2000 = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(julian_day(&j2000), J2000);
+    }
+
+    #[test]
+    fn test_utc_offset() {
+        // 14:00 at UTC+2 is noon UT
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let local = offset.with_ymd_and_hms(2000, 1, 1, 14, 0, 0).unwrap();
+        assert_eq!(julian_day(&local), J2000);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let date = NaiveDate::from_ymd_opt(1987, 4, 10)
+            .unwrap()
+            .and_hms_opt(19, 21, 0)
+            .unwrap()
+            .and_utc();
+        let back = to_datetime(julian_day(&date)).unwrap();
+        assert!((back - date).num_milliseconds().abs() < 1);
+    }
+
+    #[test]
+    fn test_delta_t() {
+        assert!((delta_t(2000.0) - 63.86).abs() < 1e-9);
+        assert!((delta_t(1900.0) - -2.79).abs() < 1e-9);
+        // Observed value for 1977 was about 47.5 s
+        assert!((delta_t(1977.0) - 47.5).abs() < 0.5);
+        // The ancient and future branches meet the historical fits smoothly
+        assert!((delta_t(-500.0) - delta_t(-500.001)).abs() < 5.0);
+        assert!((delta_t(2150.0) - delta_t(2149.999)).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_julian_ephemeris_day() {
+        let jde = julian_ephemeris_day(J2000);
+        assert!(((jde - J2000) * SECONDS_PER_DAY - 63.86).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_sidereal_time() {
+        // Meeus, example 12.a: 1987 Apr 10, 0h UT
+        let jd = 2446895.5;
+        assert!((greenwich_mean_sidereal_time(jd) - hours(13.0, 10.0, 46.3668)).abs() * 240.0 < 0.001);
+        assert!((greenwich_apparent_sidereal_time(jd) - hours(13.0, 10.0, 46.1351)).abs() * 240.0 < 0.001);
+
+        // Meeus, example 12.b: 1987 Apr 10, 19h21m00s UT
+        let jd = 2446896.30625;
+        assert!((greenwich_mean_sidereal_time(jd) - 128.7378734).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_local_sidereal_time() {
+        let jd = 2446895.5;
+        let greenwich = greenwich_apparent_sidereal_time(jd);
+        let washington = local_apparent_sidereal_time(jd, -77.0656);
+        assert!((greenwich - washington - 77.0656).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_obliquity() {
+        // Meeus, example 22.a: 23°26'36.850" on 1987 Apr 10
+        let expected = 23.0 + 26.0 / 60.0 + 36.850 / 3600.0;
+        assert!((obliquity(2446895.5) - expected).abs() * 3600.0 < 0.01);
+    }
+}
@@ -0,0 +1,382 @@
+//! Transit search: the exact times in a date range when moving bodies reach
+//! natal points, midpoints or sensitive points on a harmonic dial.
+//!
+//! A hit is a conjunction on the dial, i.e. the transiting body stands a
+//! multiple of the dial's span from the target. Each body's longitude is
+//! sampled often enough that it moves well under a quarter span between
+//! samples, then every crossing is narrowed down by bisection. Stations are
+//! handled naturally: a body that turns back over a target crosses it again.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+
+use chrono::{DateTime, Utc};
+
+use crate::astrology::chart::{Chart, PointId};
+use crate::astrology::midpoints;
+use crate::astrology::pictures::{Formula, FormulaError, Sign};
+use crate::astrology::{ephemeris, time, Body, Harmonic};
+
+/// Hit times are narrowed down to this many days, about a second
+const TIME_TOLERANCE: f64 = 1e-5;
+
+/// Longest step in days between samples, however slow the body
+const MAX_STEP: f64 = 1.0;
+
+/// Shortest step in days between samples, for very high harmonics
+const MIN_STEP: f64 = 1.0 / 1440.0;
+
+/// A little above the fastest daily motion of each body, in degrees
+pub(crate) fn max_speed(body: Body) -> f64 {
+    match body {
+        Body::Moon => 15.5,
+        Body::Mercury => 2.3,
+        Body::Venus => 1.3,
+        Body::Sun => 1.1,
+        Body::Mars => 0.8,
+        Body::Jupiter => 0.25,
+        Body::Saturn => 0.15,
+        _ => 0.1,
+    }
+}
+
+/// Longitude of a body at a Julian Day in Universal Time
+fn longitude(body: Body, jd: f64) -> f64 {
+    ephemeris::longitude(body, time::julian_ephemeris_day(jd))
+}
+
+/// Shared between a search and the thread watching it: how far the search
+/// got, and whether it should give up
+#[derive(Debug, Default)]
+pub struct SearchProgress {
+    done: AtomicUsize,
+    total: AtomicUsize,
+    cancelled: AtomicBool,
+}
+
+impl SearchProgress {
+    /// Fraction of the samples taken so far, from 0 to 1
+    pub fn fraction(&self) -> f32 {
+        let total = self.total.load(AtomicOrdering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+        self.done.load(AtomicOrdering::Relaxed) as f32 / total as f32
+    }
+
+    /// Asks the search to stop at the next sample
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Sets how many samples the work will take
+    pub(crate) fn begin(&self, total: usize) {
+        self.total.store(total, AtomicOrdering::Relaxed);
+    }
+
+    /// Counts a sample taken
+    pub(crate) fn advance(&self) {
+        self.done.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+}
+
+/// What a transit is searched for in the natal chart
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransitTarget {
+    Point(PointId),
+    Midpoint(PointId, PointId),
+    /// A sensitive point such as `SU+MO-AS`
+    Sensitive(Formula),
+}
+
+impl TransitTarget {
+    /// Natal longitudes to search for. Both midpoints of a pair are given
+    /// where they fall apart on the dial.
+    pub fn longitudes(&self, natal: &Chart, harmonic: Harmonic) -> Result<Vec<f64>, FormulaError> {
+        let position = |point: PointId| {
+            natal
+                .point(point)
+                .map(|point| point.longitude)
+                .ok_or(FormulaError::MissingPoint(point))
+        };
+        Ok(match self {
+            Self::Point(point) => vec![position(*point)?],
+            Self::Midpoint(a, b) => {
+                let near = midpoints::midpoint(position(*a)?, position(*b)?);
+                let span = 360.0 / harmonic.to_number() as f64;
+                if (180.0 % span).abs() < 1e-9 {
+                    vec![near]
+                } else {
+                    vec![near, (near + 180.0).rem_euclid(360.0)]
+                }
+            }
+            Self::Sensitive(formula) => vec![formula.longitude(natal.points())?],
+        })
+    }
+}
+
+impl FromStr for TransitTarget {
+    type Err = FormulaError;
+
+    /// Parses "MC", "SU/MO" or a formula such as "SU+MO-AS"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((a, b)) = s.split_once('/') {
+            let point = |name: &str| {
+                let name = name.trim();
+                PointId::from_abbreviation(name).ok_or_else(|| FormulaError::UnknownPoint(name.to_string()))
+            };
+            return Ok(Self::Midpoint(point(a)?, point(b)?));
+        }
+        let formula: Formula = s.parse()?;
+        match formula.terms() {
+            [term] if term.sign == Sign::Plus => Ok(Self::Point(term.point)),
+            _ => Ok(Self::Sensitive(formula)),
+        }
+    }
+}
+
+impl fmt::Display for TransitTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Point(point) => write!(f, "{}", point.abbreviation()),
+            Self::Midpoint(a, b) => write!(f, "{}/{}", a.abbreviation(), b.abbreviation()),
+            Self::Sensitive(formula) => write!(f, "{}", formula),
+        }
+    }
+}
+
+/// A moment a transiting body reaches a target on the dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitHit {
+    pub body: Body,
+    pub target: TransitTarget,
+    /// Julian Day in Universal Time of the exact hit
+    pub jd: f64,
+    /// Longitude of the transiting body at the hit
+    pub longitude: f64,
+    pub retrograde: bool,
+}
+
+impl TransitHit {
+    pub fn datetime(&self) -> Option<DateTime<Utc>> {
+        time::to_datetime(self.jd)
+    }
+}
+
+/// Searches for the transits of some bodies over natal targets on a dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitSearch {
+    pub bodies: Vec<Body>,
+    pub harmonic: Harmonic,
+}
+
+impl TransitSearch {
+    pub fn new(bodies: Vec<Body>, harmonic: Harmonic) -> Self {
+        Self { bodies, harmonic }
+    }
+
+    /// Returns every hit between two Julian Days, in time order
+    pub fn find(
+        &self,
+        natal: &Chart,
+        targets: &[TransitTarget],
+        start: f64,
+        end: f64,
+    ) -> Result<Vec<TransitHit>, FormulaError> {
+        self.find_with_progress(natal, targets, start, end, &SearchProgress::default())
+    }
+
+    /// Like [`Self::find`], reporting progress as it goes. Once cancelled it
+    /// stops early with the hits found so far.
+    pub fn find_with_progress(
+        &self,
+        natal: &Chart,
+        targets: &[TransitTarget],
+        start: f64,
+        end: f64,
+        progress: &SearchProgress,
+    ) -> Result<Vec<TransitHit>, FormulaError> {
+        let span = 360.0 / self.harmonic.to_number() as f64;
+        let targets = targets
+            .iter()
+            .map(|target| Ok((target, target.longitudes(natal, self.harmonic)?)))
+            .collect::<Result<Vec<_>, FormulaError>>()?;
+
+        let steps: Vec<(Body, f64, usize)> = self
+            .bodies
+            .iter()
+            .map(|&body| {
+                let step = (span / 4.0 / max_speed(body)).clamp(MIN_STEP, MAX_STEP);
+                (body, step, ((end - start) / step).ceil().max(0.0) as usize)
+            })
+            .collect();
+        let total = steps.iter().map(|(_, _, count)| count + 1).sum();
+        progress.begin(total);
+
+        let mut hits = Vec::new();
+        'bodies: for (body, step, count) in steps {
+            let mut samples: Vec<(f64, f64)> = Vec::with_capacity(count + 1);
+            for i in 0..=count {
+                if progress.is_cancelled() {
+                    break 'bodies;
+                }
+                let jd = (start + i as f64 * step).min(end);
+                samples.push((jd, longitude(body, jd)));
+                progress.advance();
+            }
+
+            for (target, longitudes) in &targets {
+                for &target_longitude in longitudes {
+                    // Distance from the target on the dial, in [-span/2, span/2)
+                    let offset = |longitude: f64| {
+                        (longitude - target_longitude + span / 2.0).rem_euclid(span) - span / 2.0
+                    };
+                    for pair in samples.windows(2) {
+                        let ((t0, l0), (t1, l1)) = (pair[0], pair[1]);
+                        let (f0, f1) = (offset(l0), offset(l1));
+                        // A crossing, not the jump where the offset wraps round
+                        if (f0 < 0.0) == (f1 < 0.0) || (f1 - f0).abs() >= span / 2.0 {
+                            continue;
+                        }
+                        let jd = bisect(|jd| offset(longitude(body, jd)), t0, t1, f0 < 0.0);
+                        let position = ephemeris::calculate(body, time::julian_ephemeris_day(jd));
+                        hits.push(TransitHit {
+                            body,
+                            target: (*target).clone(),
+                            jd,
+                            longitude: position.longitude,
+                            retrograde: position.is_retrograde(),
+                        });
+                    }
+                }
+            }
+        }
+
+        hits.sort_by(|a, b| a.jd.partial_cmp(&b.jd).unwrap_or(Ordering::Equal));
+        Ok(hits)
+    }
+}
+
+/// Narrows down the time a function changes sign between two Julian Days
+fn bisect(f: impl Fn(f64) -> f64, mut low: f64, mut high: f64, negative_at_low: bool) -> f64 {
+    while high - low > TIME_TOLERANCE {
+        let middle = (low + high) / 2.0;
+        if (f(middle) < 0.0) == negative_at_low {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::{BirthData, ChartPoint};
+    use chrono::{FixedOffset, TimeZone};
+
+    fn natal() -> Chart {
+        let offset = FixedOffset::east_opt(3600).unwrap();
+        let datetime = offset.with_ymd_and_hms(1980, 5, 17, 14, 30, 0).unwrap();
+        Chart::new("Sample", BirthData::new(datetime, 53.55, 10.0).unwrap())
+    }
+
+    fn jd(year: i32, month: u32, day: u32) -> f64 {
+        time::julian_day(&Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        let mc: TransitTarget = "mc".parse().unwrap();
+        assert_eq!(mc, TransitTarget::Point(PointId::Midheaven));
+        let midpoint: TransitTarget = "SU / MO".parse().unwrap();
+        assert_eq!(midpoint.to_string(), "SU/MO");
+        let sensitive: TransitTarget = "SU+MO-AS".parse().unwrap();
+        assert!(matches!(sensitive, TransitTarget::Sensitive(_)));
+        assert_eq!("SU/XX".parse::<TransitTarget>(), Err(FormulaError::UnknownPoint("XX".to_string())));
+    }
+
+    #[test]
+    fn test_solar_return() {
+        // The Sun returns to its natal place once a year, around the birthday
+        let chart = natal();
+        let search = TransitSearch::new(vec![Body::Sun], Harmonic::First);
+        let target = TransitTarget::Point(PointId::Body(Body::Sun));
+        let hits = search.find(&chart, &[target], jd(2024, 1, 1), jd(2025, 1, 1)).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].datetime().unwrap().format("%m-%d").to_string(), "05-17");
+        let natal_sun = chart.point(PointId::Body(Body::Sun)).unwrap().longitude;
+        assert!((hits[0].longitude - natal_sun).abs() < 1e-4);
+        assert!(!hits[0].retrograde);
+    }
+
+    #[test]
+    fn test_harmonic_hits() {
+        // On the 90° dial the Sun hits a point four times a year
+        let chart = natal();
+        let search = TransitSearch::new(vec![Body::Sun], Harmonic::Fourth);
+        let target: TransitTarget = "MC".parse().unwrap();
+        let hits = search.find(&chart, &[target], jd(2024, 1, 1), jd(2025, 1, 1)).unwrap();
+        assert_eq!(hits.len(), 4);
+        let mc = chart.point(PointId::Midheaven).unwrap().longitude;
+        for hit in &hits {
+            let offset = (hit.longitude - mc).rem_euclid(90.0);
+            assert!(offset.min(90.0 - offset) < 1e-4, "offset {}", offset);
+        }
+    }
+
+    #[test]
+    fn test_progress_and_cancel() {
+        let chart = natal();
+        let search = TransitSearch::new(vec![Body::Sun, Body::Mars], Harmonic::First);
+        let targets = [TransitTarget::Point(PointId::Body(Body::Sun))];
+        let progress = SearchProgress::default();
+        let hits = search
+            .find_with_progress(&chart, &targets, jd(2024, 1, 1), jd(2025, 1, 1), &progress)
+            .unwrap();
+        assert_eq!(hits, search.find(&chart, &targets, jd(2024, 1, 1), jd(2025, 1, 1)).unwrap());
+        assert_eq!(progress.fraction(), 1.0);
+
+        let cancelled = SearchProgress::default();
+        cancelled.cancel();
+        let hits = search
+            .find_with_progress(&chart, &targets, jd(2024, 1, 1), jd(2025, 1, 1), &cancelled)
+            .unwrap();
+        assert!(hits.is_empty());
+        assert_eq!(cancelled.fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_retrograde_passes() {
+        // Jupiter turned retrograde on 2023 September 4, so a point two degrees
+        // behind the station is crossed direct, retrograde and direct again
+        let station = longitude(Body::Jupiter, jd(2023, 9, 4));
+        let natal = natal();
+        let point = ChartPoint {
+            id: PointId::Body(Body::Sun),
+            longitude: (station - 2.0).rem_euclid(360.0),
+            latitude: 0.0,
+            speed: 0.0,
+        };
+        let chart = Chart::from_points(natal.name, natal.birth, vec![point]);
+
+        let search = TransitSearch::new(vec![Body::Jupiter], Harmonic::First);
+        let target = TransitTarget::Point(PointId::Body(Body::Sun));
+        let hits = search.find(&chart, &[target], jd(2023, 6, 1), jd(2024, 6, 1)).unwrap();
+        let retrograde: Vec<bool> = hits.iter().map(|hit| hit.retrograde).collect();
+        assert_eq!(retrograde, [false, true, false]);
+
+        let missing = TransitTarget::Point(PointId::Midheaven);
+        assert_eq!(
+            search.find(&chart, &[missing], jd(2023, 6, 1), jd(2023, 7, 1)),
+            Err(FormulaError::MissingPoint(PointId::Midheaven))
+        );
+    }
+}
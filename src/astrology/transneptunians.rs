@@ -0,0 +1,204 @@
+//! The eight hypothetical transneptunian points of the Hamburg School.
+//!
+//! Positions follow Keplerian orbits whose elements were fitted by James Neely
+//! to the Witte/Sieggrün ephemerides. The elements refer to the ecliptic and
+//! equinox of 1900 January 0.5; geocentric positions for the chart are
+//! available through [`crate::astrology::ephemeris::calculate`].
+
+use crate::astrology::Body;
+
+/// Julian Ephemeris Day of the epoch B1900.0 used by the elements
+pub const J1900: f64 = 2415020.0;
+
+/// Gaussian gravitational constant in degrees per day
+const GAUSSIAN_GRAVITY: f64 = 0.985_607_668_6;
+
+/// Keplerian orbital elements, angles in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Mean anomaly at J1900
+    pub mean_anomaly: f64,
+    /// Semi-major axis in AU
+    pub semi_major_axis: f64,
+    pub eccentricity: f64,
+    /// Argument of perihelion
+    pub perihelion: f64,
+    /// Longitude of the ascending node
+    pub node: f64,
+    pub inclination: f64,
+}
+
+/// Heliocentric ecliptic coordinates in degrees and AU, equinox J1900
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeliocentricPosition {
+    pub longitude: f64,
+    pub latitude: f64,
+    pub radius: f64,
+}
+
+const fn elements(
+    mean_anomaly: f64,
+    semi_major_axis: f64,
+    eccentricity: f64,
+    perihelion: f64,
+    node: f64,
+    inclination: f64,
+) -> OrbitalElements {
+    OrbitalElements {
+        mean_anomaly,
+        semi_major_axis,
+        eccentricity,
+        perihelion,
+        node,
+        inclination,
+    }
+}
+
+/// Neely's elements for Cupido through Poseidon, in `Body::TRANSNEPTUNIANS` order
+const ELEMENTS: [OrbitalElements; 8] = [
+    elements(163.7409, 40.99837, 0.00460, 171.4333, 129.8325, 1.0833),
+    elements(27.6496, 50.66744, 0.00245, 148.1796, 161.3339, 1.0500),
+    elements(165.1232, 59.21436, 0.00120, 299.0440, 0.0, 0.0),
+    elements(169.0193, 64.81690, 0.00305, 208.8801, 0.0, 0.0),
+    elements(138.0533, 70.29949, 0.0, 0.0, 0.0, 0.0),
+    elements(351.3350, 73.62765, 0.0, 0.0, 0.0, 0.0),
+    elements(55.8983, 77.25568, 0.0, 0.0, 0.0, 0.0),
+    elements(165.5163, 83.66907, 0.0, 0.0, 0.0, 0.0),
+];
+
+impl OrbitalElements {
+    /// Returns the elements for a transneptunian point, or `None` for other bodies
+    pub fn of(body: Body) -> Option<&'static OrbitalElements> {
+        Body::TRANSNEPTUNIANS
+            .iter()
+            .position(|b| *b == body)
+            .map(|i| &ELEMENTS[i])
+    }
+
+    /// Mean daily motion in degrees
+    pub fn mean_motion(&self) -> f64 {
+        GAUSSIAN_GRAVITY / self.semi_major_axis.powf(1.5)
+    }
+
+    /// Orbital period in Julian years
+    pub fn period(&self) -> f64 {
+        360.0 / self.mean_motion() / 365.25
+    }
+
+    /// Position on the orbit for a Julian Ephemeris Day
+    pub fn heliocentric(&self, jde: f64) -> HeliocentricPosition {
+        let e = self.eccentricity;
+        let mean_anomaly = (self.mean_anomaly + self.mean_motion() * (jde - J1900))
+            .rem_euclid(360.0)
+            .to_radians();
+        let eccentric_anomaly = solve_kepler(mean_anomaly, e);
+
+        let (sin_e, cos_e) = eccentric_anomaly.sin_cos();
+        let x = cos_e - e;
+        let y = (1.0 - e * e).sqrt() * sin_e;
+        let radius = self.semi_major_axis * x.hypot(y);
+        let true_anomaly = y.atan2(x);
+
+        let (sin_u, cos_u) = (self.perihelion.to_radians() + true_anomaly).sin_cos();
+        let (sin_i, cos_i) = self.inclination.to_radians().sin_cos();
+        let longitude = self.node + (sin_u * cos_i).atan2(cos_u).to_degrees();
+
+        HeliocentricPosition {
+            longitude: longitude.rem_euclid(360.0),
+            latitude: (sin_u * sin_i).asin().to_degrees(),
+            radius,
+        }
+    }
+}
+
+/// Heliocentric position of a transneptunian point, or `None` for other bodies
+pub fn heliocentric(body: Body, jde: f64) -> Option<HeliocentricPosition> {
+    OrbitalElements::of(body).map(|elements| elements.heliocentric(jde))
+}
+
+/// Solves Kepler's equation for the eccentric anomaly (radians)
+fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let mut anomaly = mean_anomaly;
+    for _ in 0..10 {
+        let delta = (anomaly - eccentricity * anomaly.sin() - mean_anomaly)
+            / (1.0 - eccentricity * anomaly.cos());
+        anomaly -= delta;
+        if delta.abs() < 1e-12 {
+            break;
+        }
+    }
+    anomaly
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::ephemeris;
+
+    #[test]
+    fn test_only_transneptunians_have_elements() {
+        for body in Body::TRANSNEPTUNIANS {
+            assert!(OrbitalElements::of(body).is_some(), "{:?}", body);
+        }
+        for body in Body::CLASSICAL {
+            assert!(heliocentric(body, J1900).is_none(), "{:?}", body);
+        }
+    }
+
+    #[test]
+    fn test_periods() {
+        // Cupido takes about 262 years to circle the zodiac, Poseidon about 766
+        let cupido = OrbitalElements::of(Body::Cupido).unwrap();
+        let poseidon = OrbitalElements::of(Body::Poseidon).unwrap();
+        assert!((cupido.period() - 262.5).abs() < 0.5);
+        assert!((poseidon.period() - 765.3).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_circular_orbit_at_epoch() {
+        let apollon = heliocentric(Body::Apollon, J1900).unwrap();
+        assert!((apollon.longitude - 138.0533).abs() < 1e-9);
+        assert_eq!(apollon.latitude, 0.0);
+        assert!((apollon.radius - 70.29949).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_geocentric_snapshot() {
+        // This model's own geocentric positions at J2000.0, to the arc minute.
+        // They guard against accidental changes only; they have not yet been
+        // checked against the printed Rosenkreuz ephemeris.
+        let expected = [
+            (Body::Cupido, 243.897),
+            (Body::Hades, 78.180),
+            (Body::Zeus, 185.388),
+            (Body::Kronos, 87.798),
+            (Body::Apollon, 201.307),
+            (Body::Admetos, 49.115),
+            (Body::Vulkanus, 110.439),
+            (Body::Poseidon, 214.558),
+        ];
+        for (body, longitude) in expected {
+            let position = ephemeris::calculate(body, 2451545.0);
+            assert!((position.longitude - longitude).abs() < 1.0 / 60.0, "{:?}", body);
+        }
+    }
+
+    #[test]
+    fn test_geocentric_loop() {
+        // The Earth's orbit makes Cupido swing about 1.4° either side of its
+        // heliocentric place, turning retrograde once a year. The longitudes
+        // are this model's output, not table values.
+        let helio = heliocentric(Body::Cupido, 2433282.5).unwrap();
+        let geo = ephemeris::calculate(Body::Cupido, 2433282.5);
+        assert!((173.153 - helio.longitude).abs() < 1.0 / 60.0);
+        assert!((175.176 - geo.longitude).abs() < 1.0 / 60.0);
+        assert!(geo.is_retrograde());
+    }
+
+    #[test]
+    fn test_kepler_solution() {
+        let e = 0.2;
+        let anomaly = solve_kepler(1.0, e);
+        assert!((anomaly - e * anomaly.sin() - 1.0).abs() < 1e-12);
+    }
+}
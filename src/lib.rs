@@ -0,0 +1,4 @@
+pub mod app;
+pub mod astrology;
+pub mod ui;
+pub mod utils;
@@ -1,9 +1,4 @@
-mod app;
-mod astrology;
-mod ui;
-mod utils;
-
-use app::DialApp;
+use uranian_astrology::app::DialApp;
 use eframe::egui;
 
 fn main() -> Result<(), eframe::Error> {
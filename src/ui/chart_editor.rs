@@ -0,0 +1,178 @@
+use egui::{self, Ui};
+use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone};
+use crate::astrology::chart::{BirthData, Chart};
+
+/// Form for entering birth data and saving or loading charts
+pub struct ChartEditor {
+    name: String,
+    date: String,
+    time: String,
+    utc_offset: String,
+    latitude: String,
+    longitude: String,
+    path: String,
+    status: Option<String>,
+}
+
+impl Default for ChartEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChartEditor {
+    pub fn new() -> Self {
+        Self {
+            name: String::new(),
+            date: "2000-01-01".to_string(),
+            time: "12:00".to_string(),
+            utc_offset: "+00:00".to_string(),
+            latitude: "0.0".to_string(),
+            longitude: "0.0".to_string(),
+            path: "chart.json".to_string(),
+            status: None,
+        }
+    }
+
+    /// Fills the form from an existing chart
+    pub fn load_from(&mut self, chart: &Chart) {
+        let datetime = chart.birth.datetime;
+        self.name = chart.name.clone();
+        self.date = datetime.format("%Y-%m-%d").to_string();
+        self.time = datetime.format("%H:%M:%S").to_string();
+        self.utc_offset = datetime.offset().to_string();
+        self.latitude = chart.birth.latitude.to_string();
+        self.longitude = chart.birth.longitude.to_string();
+    }
+
+    /// Parses the form into birth data, describing the first invalid field
+    pub fn birth_data(&self) -> Result<BirthData, String> {
+        let date = NaiveDate::parse_from_str(self.date.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date \"{}\", expected YYYY-MM-DD", self.date))?;
+        let time = NaiveTime::parse_from_str(self.time.trim(), "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(self.time.trim(), "%H:%M"))
+            .map_err(|_| format!("Invalid time \"{}\", expected HH:MM or HH:MM:SS", self.time))?;
+        let offset: FixedOffset = self.utc_offset.trim().parse()
+            .map_err(|_| format!("Invalid UTC offset \"{}\", expected e.g. +02:00", self.utc_offset))?;
+        let latitude: f64 = self.latitude.trim().parse()
+            .map_err(|_| format!("Invalid latitude \"{}\"", self.latitude))?;
+        let longitude: f64 = self.longitude.trim().parse()
+            .map_err(|_| format!("Invalid longitude \"{}\"", self.longitude))?;
+
+        let datetime = offset
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .ok_or_else(|| "Ambiguous local time".to_string())?;
+        BirthData::new(datetime, latitude, longitude).map_err(|err| err.to_string())
+    }
+
+    /// Shows the form. Returns a chart when one was calculated or loaded.
+    pub fn ui(&mut self, ui: &mut Ui, current: Option<&Chart>) -> Option<Chart> {
+        let mut result = None;
+
+        egui::Grid::new("chart_editor").num_columns(2).show(ui, |ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut self.name);
+            ui.end_row();
+            ui.label("Date:");
+            ui.text_edit_singleline(&mut self.date);
+            ui.end_row();
+            ui.label("Time:");
+            ui.text_edit_singleline(&mut self.time);
+            ui.end_row();
+            ui.label("UTC offset:");
+            ui.text_edit_singleline(&mut self.utc_offset);
+            ui.end_row();
+            ui.label("Latitude (N+):");
+            ui.text_edit_singleline(&mut self.latitude);
+            ui.end_row();
+            ui.label("Longitude (E+):");
+            ui.text_edit_singleline(&mut self.longitude);
+            ui.end_row();
+        });
+
+        if ui.button("Calculate").clicked() {
+            match self.birth_data() {
+                Ok(birth) => {
+                    result = Some(Chart::new(self.name.trim(), birth));
+                    self.status = None;
+                }
+                Err(message) => self.status = Some(message),
+            }
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("File:");
+            ui.text_edit_singleline(&mut self.path);
+        });
+        ui.horizontal(|ui| {
+            if ui.add_enabled(current.is_some(), egui::Button::new("Save")).clicked() {
+                if let Some(chart) = current {
+                    self.status = Some(match chart.save(&self.path) {
+                        Ok(()) => format!("Saved to {}", self.path),
+                        Err(err) => err.to_string(),
+                    });
+                }
+            }
+            if ui.button("Load").clicked() {
+                match Chart::load(&self.path) {
+                    Ok(chart) => {
+                        self.load_from(&chart);
+                        self.status = Some(format!("Loaded {}", self.path));
+                        result = Some(chart);
+                    }
+                    Err(err) => self.status = Some(err.to_string()),
+                }
+            }
+        });
+
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_birth_data_parsing() {
+        let mut editor = ChartEditor::new();
+        editor.date = "1980-05-17".to_string();
+        editor.time = "14:30".to_string();
+        editor.utc_offset = "+02:00".to_string();
+        editor.latitude = "53.55".to_string();
+        editor.longitude = "10.0".to_string();
+
+        let birth = editor.birth_data().unwrap();
+        assert_eq!(birth.datetime.to_rfc3339(), "1980-05-17T14:30:00+02:00");
+        assert_eq!(birth.latitude, 53.55);
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        let mut editor = ChartEditor::new();
+        editor.time = "25:00".to_string();
+        assert!(editor.birth_data().unwrap_err().contains("time"));
+
+        editor.time = "12:00".to_string();
+        editor.latitude = "north".to_string();
+        assert!(editor.birth_data().unwrap_err().contains("latitude"));
+    }
+
+    #[test]
+    fn test_form_round_trip() {
+        let mut editor = ChartEditor::new();
+        editor.name = "Sample".to_string();
+        editor.utc_offset = "-05:00".to_string();
+        let chart = Chart::new("Sample", editor.birth_data().unwrap());
+
+        let mut other = ChartEditor::new();
+        other.load_from(&chart);
+        assert_eq!(other.birth_data().unwrap(), chart.birth);
+    }
+}
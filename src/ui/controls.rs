@@ -1,22 +1,13 @@
 use egui::{self, Ui};
 use crate::astrology::harmonics::Harmonic;
 
+#[derive(Default)]
 pub struct DialControls {
     harmonic: Harmonic,
     shift_pressed: bool,
     ctrl_pressed: bool,
 }
 
-impl Default for DialControls {
-    fn default() -> Self {
-        Self {
-            harmonic: Harmonic::default(),
-            shift_pressed: false,
-            ctrl_pressed: false,
-        }
-    }
-}
-
 impl DialControls {
     pub fn new() -> Self {
         Self::default()
@@ -35,7 +26,7 @@ impl DialControls {
         self.ctrl_pressed = modifiers.ctrl;
     }
     
-    pub fn get_increment(&self) -> f32 {
+    pub fn get_increment(&self) -> f64 {
         match (self.shift_pressed, self.ctrl_pressed) {
             (true, true) => 1.0 / 3600.0,  // 1 second
             (false, true) => 1.0 / 60.0,   // 1 minute
@@ -85,6 +76,7 @@ impl DialControls {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
     
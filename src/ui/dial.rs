@@ -1,17 +1,215 @@
 use eframe::egui;
 use std::f32::consts::PI;
-use crate::astrology::{DegreePosition, Harmonic};
+use crate::astrology::antiscia::reflect_across;
+use crate::astrology::harmonics::calculate_harmonic_position;
+use crate::astrology::houses::Houses;
+use crate::astrology::signs::format_in_sign;
+use crate::astrology::{DegreePosition, Harmonic, PointId};
+
+/// How close in degrees the red arm must be to a point to count as pointing at it
+const POINTER_TOLERANCE: f32 = 1.0;
+
+/// Most arms drawn before the harmonic arms are left out
+const MAX_DRAWN_ARMS: u32 = 720;
+
+/// Most harmonic spans whose arms get a degree label
+const MAX_LABELLED_SPANS: u32 = 24;
+
+/// Smallest gap in degrees kept between neighbouring glyphs on the ring
+const GLYPH_SEPARATION: f32 = 5.0;
+
+/// Distance in pixels between the concentric rings of a multi-chart dial
+const RING_SPACING: f32 = 50.0;
+
+/// Colour of the first ring when only points are given
+const DEFAULT_RING_COLOR: egui::Color32 = egui::Color32::from_rgb(30, 30, 140);
+
+/// How near in pixels the pointer must come to a glyph to show its tooltip
+const GLYPH_HOVER_RADIUS: f32 = 10.0;
+
+/// Seconds an animated jump to a position takes at normal speed
+const GO_TO_SECONDS: f32 = 0.4;
+
+/// Seconds the points take to glide to their places on a new harmonic face at normal speed
+const HARMONIC_SECONDS: f32 = 0.6;
+
+/// Longest frame time an animation steps by, so it does not skip after a pause
+const MAX_FRAME_SECONDS: f32 = 0.1;
+
+/// Degrees the dial jumps with PageUp and PageDown
+const PAGE_JUMP: DegreePosition = DegreePosition::new(30, 0, 0);
+
+/// Points of trackpad scrolling per step of the dial, as much as one line of a mouse wheel
+const SCROLL_POINTS_PER_STEP: f64 = 50.0;
+
+/// How quickly a flicked dial slows down: its speed falls by e each 1/FRICTION seconds
+const FRICTION: f64 = 4.0;
+
+/// Slowest release in degrees of the face per second that sets the dial coasting
+const MIN_FLICK_SPEED: f64 = 30.0;
+
+/// Speed in degrees of the face per second below which a coasting dial stops
+const STOP_SPEED: f64 = 2.0;
+
+/// Tick spacings in arcseconds the face can use, from 1' up to 90°
+const TICK_STEPS: [u32; 13] = [
+    60, 120, 300, 600, 900, 1800, 3600, 18_000, 36_000, 54_000, 108_000, 162_000, 324_000,
+];
+
+/// Returns the minor, medium and labelled tick spacing in arcseconds for a
+/// face spanning the given degrees, about 360, 72 and 12 ticks round the ring
+pub fn tick_steps(span: f64) -> (u32, u32, u32) {
+    let arcseconds = span * 3600.0;
+    let step = |count: f64| {
+        TICK_STEPS
+            .iter()
+            .copied()
+            .find(|&step| step as f64 >= arcseconds / count - 1e-6)
+            .unwrap_or(TICK_STEPS[TICK_STEPS.len() - 1])
+    };
+    (step(360.0), step(72.0), step(12.0))
+}
+
+/// Eases an animation in and out over progress from 0 to 1
+fn ease(progress: f32) -> f32 {
+    progress * progress * (3.0 - 2.0 * progress)
+}
+
+/// Fans out angles in degrees so no two are closer than a separation.
+///
+/// Clustered angles are spread evenly around the mean of the cluster, so a
+/// stellium keeps its place on the ring. Returns the new angles in input order.
+pub fn spread_angles(angles: &[f32], min_separation: f32) -> Vec<f32> {
+    let n = angles.len();
+    if n < 2 {
+        return angles.to_vec();
+    }
+    let separation = min_separation.min(360.0 / n as f32);
+    
+    let mut members: Vec<usize> = (0..n).collect();
+    members.sort_by(|&a, &b| angles[a].rem_euclid(360.0).total_cmp(&angles[b].rem_euclid(360.0)));
+    let sorted: Vec<f32> = members.iter().map(|&i| angles[i].rem_euclid(360.0)).collect();
+    
+    // Cut the circle at the widest gap so a cluster is unlikely to straddle the cut
+    let gap = |i: usize| (sorted[(i + 1) % n] - sorted[i]).rem_euclid(360.0);
+    let widest = (0..n).max_by(|&a, &b| gap(a).total_cmp(&gap(b))).unwrap_or(0);
+    let start = (widest + 1) % n;
+    members.rotate_left(start);
+    let mut values: Vec<f32> = (0..n)
+        .map(|k| sorted[(start + k) % n] + if start + k >= n { 360.0 } else { 0.0 })
+        .collect();
+    
+    // Clusters as (first member, length, centre), merged until none overlap
+    let mut clusters: Vec<(usize, usize, f32)> = (0..n).map(|k| (k, 1, values[k])).collect();
+    let first_of = |c: (usize, usize, f32)| c.2 - (c.1 - 1) as f32 * separation / 2.0;
+    let last_of = |c: (usize, usize, f32)| c.2 + (c.1 - 1) as f32 * separation / 2.0;
+    loop {
+        let mut merged = false;
+        let mut i = 0;
+        while i + 1 < clusters.len() {
+            let (a, b) = (clusters[i], clusters[i + 1]);
+            if first_of(b) - last_of(a) < separation - 1e-4 {
+                let len = a.1 + b.1;
+                let centre = values[a.0..a.0 + len].iter().sum::<f32>() / len as f32;
+                clusters[i] = (a.0, len, centre);
+                clusters.remove(i + 1);
+                merged = true;
+            } else {
+                i += 1;
+            }
+        }
+        
+        // Spreading may still close the gap across the cut: carry the last
+        // cluster round to the front and try again
+        let (first, last) = (clusters[0], clusters[clusters.len() - 1]);
+        if clusters.len() > 1 && first_of(first) + 360.0 - last_of(last) < separation - 1e-4 {
+            let count = last.1;
+            members.rotate_right(count);
+            values.rotate_right(count);
+            for value in &mut values[..count] {
+                *value -= 360.0;
+            }
+            clusters.pop();
+            for cluster in &mut clusters {
+                cluster.0 += count;
+            }
+            clusters.insert(0, (0, count, last.2 - 360.0));
+            merged = true;
+        }
+        if !merged {
+            break;
+        }
+    }
+    
+    let mut result = vec![0.0; n];
+    for (first, len, centre) in clusters {
+        for j in 0..len {
+            let position = centre + (j as f32 - (len - 1) as f32 / 2.0) * separation;
+            result[members[first + j]] = position.rem_euclid(360.0);
+        }
+    }
+    result
+}
+
+/// Widens the glyph separation on inner rings, whose circumference is shorter
+fn ring_scale(ring: usize) -> f32 {
+    1.0 + ring as f32 * 0.35
+}
+
+/// One chart's points, drawn as a concentric ring of the dial
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialRing {
+    pub label: String,
+    pub points: Vec<(PointId, DegreePosition)>,
+    pub color: egui::Color32,
+}
+
+impl DialRing {
+    pub fn new(label: impl Into<String>, points: Vec<(PointId, DegreePosition)>, color: egui::Color32) -> Self {
+        Self {
+            label: label.into(),
+            points,
+            color,
+        }
+    }
+}
+
+/// A point as laid out on the ring: its exact place and where its glyph went
+struct PlacedPoint {
+    ring: usize,
+    id: PointId,
+    position: DegreePosition,
+    exact: egui::Pos2,
+    glyph: egui::Pos2,
+}
 
 pub struct Dial {
     center: egui::Pos2,
     radius: f32,
     current_position: DegreePosition,
     harmonic: Harmonic,
+    harmonic_face: bool,
     is_dragging: bool,
     drag_start_pos: Option<egui::Pos2>,
-    drag_start_angle: f32,
+    drag_start_angle: f64,
     shift_pressed: bool,
     ctrl_pressed: bool,
+    rings: Vec<DialRing>,
+    houses: Option<Houses>,
+    aspect_lines: Vec<(DegreePosition, DegreePosition, egui::Color32)>,
+    mirror_axis: bool,
+    /// A jump in progress: where it started, where it ends and how far along it is
+    animation: Option<(DegreePosition, DegreePosition, f32)>,
+    /// The face easing from the harmonic number before, and how far along it is
+    harmonic_tween: Option<(f64, f32)>,
+    /// Coasting after a flick: the unsnapped position and its speed, in degrees and degrees per second
+    momentum: Option<(f64, f64)>,
+    /// Speed of the drag in degrees per second, smoothed over the last frames
+    drag_velocity: f64,
+    animation_speed: f32,
+    inertia: bool,
+    /// Scrolling short of a whole step, kept for the next scroll
+    scroll_remainder: f64,
 }
 
 impl Default for Dial {
@@ -27,16 +225,28 @@ impl Dial {
             radius: 200.0,
             current_position: DegreePosition::from_degrees(0.0),
             harmonic: Harmonic::default(),
+            harmonic_face: true,
             is_dragging: false,
             drag_start_pos: None,
             drag_start_angle: 0.0,
             shift_pressed: false,
             ctrl_pressed: false,
+            rings: Vec::new(),
+            houses: None,
+            aspect_lines: Vec::new(),
+            mirror_axis: false,
+            animation: None,
+            harmonic_tween: None,
+            momentum: None,
+            drag_velocity: 0.0,
+            animation_speed: 1.0,
+            inertia: true,
+            scroll_remainder: 0.0,
         }
     }
     
     /// Gets the increment based on modifier keys
-    pub fn get_increment(&self) -> f32 {
+    pub fn get_increment(&self) -> f64 {
         match (self.shift_pressed, self.ctrl_pressed) {
             (true, true) => 1.0 / 3600.0,  // 1 second
             (false, true) => 1.0 / 60.0,   // 1 minute
@@ -46,7 +256,7 @@ impl Dial {
     }
     
     /// Snaps angle to the current increment
-    pub fn snap_angle(&self, angle: f32) -> f32 {
+    pub fn snap_angle(&self, angle: f64) -> f64 {
         let increment = self.get_increment();
         (angle / increment).round() * increment
     }    
@@ -57,6 +267,103 @@ impl Dial {
     
     pub fn set_position(&mut self, position: DegreePosition) {
         self.current_position = position;
+        self.animation = None;
+        self.momentum = None;
+    }
+    
+    /// Turns the red arm to a position, straight away or by an animation that
+    /// takes the shorter way round the face
+    pub fn go_to(&mut self, position: DegreePosition, animate: bool) {
+        if animate && self.animation_speed > 0.0 && position != self.current_position {
+            self.animation = Some((self.current_position, position, 0.0));
+            self.momentum = None;
+        } else {
+            self.set_position(position);
+        }
+    }
+    
+    pub fn animation_speed(&self) -> f32 {
+        self.animation_speed
+    }
+    
+    /// Scales how fast jumps and harmonic changes play, 1 being normal and 0
+    /// turning the animations off
+    pub fn set_animation_speed(&mut self, speed: f32) {
+        self.animation_speed = speed.max(0.0);
+    }
+    
+    pub fn inertia(&self) -> bool {
+        self.inertia
+    }
+    
+    /// Lets the dial coast on and slow down when let go of in the middle of a flick
+    pub fn set_inertia(&mut self, inertia: bool) {
+        self.inertia = inertia;
+        if !inertia {
+            self.stop_momentum();
+        }
+    }
+    
+    /// Where the dial is, or is turning to
+    pub fn target_position(&self) -> DegreePosition {
+        self.animation.map_or(self.current_position, |(_, target, _)| target)
+    }
+    
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some() || self.harmonic_tween.is_some() || self.momentum.is_some()
+    }
+    
+    /// Moves the animations on by a frame, landing exactly on the target of a
+    /// jump and on a snapped position when coasting stops
+    fn advance_animation(&mut self, dt: f32) {
+        let dt = dt.min(MAX_FRAME_SECONDS);
+        
+        if let Some((start, target, progress)) = self.animation {
+            let progress = progress + dt * self.animation_speed / GO_TO_SECONDS;
+            if progress >= 1.0 {
+                self.set_position(target);
+            } else {
+                // Head for the place on the face nearest the start that shows the target
+                let span = self.face_span() * 3600.0;
+                let offset = (target.subtract(start).arcseconds() as f64 + span / 2.0).rem_euclid(span) - span / 2.0;
+                let waypoint = start.add(DegreePosition::from_arcseconds(offset.round() as i64));
+                self.current_position = start.interpolate(waypoint, ease(progress) as f64);
+                self.animation = Some((start, target, progress));
+            }
+        }
+        
+        if let Some((from, progress)) = self.harmonic_tween {
+            let progress = progress + dt * self.animation_speed / HARMONIC_SECONDS;
+            self.harmonic_tween = (progress < 1.0).then_some((from, progress));
+        }
+        
+        if let Some((angle, velocity)) = self.momentum {
+            // Integrated exactly over the frame, so the dial coasts as far whatever the frame rate
+            let decay = (-FRICTION * dt as f64).exp();
+            let angle = angle + velocity * (1.0 - decay) / FRICTION;
+            let velocity = velocity * decay;
+            self.current_position = DegreePosition::from_degrees(angle);
+            self.momentum = Some((angle, velocity));
+            if velocity.abs() * 360.0 / self.face_span() < STOP_SPEED {
+                self.stop_momentum();
+            }
+        }
+    }
+    
+    /// Sets a released dial coasting if it was flicked fast enough
+    fn start_momentum(&mut self) {
+        let face_speed = self.drag_velocity.abs() * 360.0 / self.face_span();
+        if self.inertia && face_speed >= MIN_FLICK_SPEED {
+            self.momentum = Some((self.drag_start_angle, self.drag_velocity));
+        }
+        self.drag_velocity = 0.0;
+    }
+    
+    /// Brings a coasting dial to rest on the nearest step of the current increment
+    fn stop_momentum(&mut self) {
+        if let Some((angle, _)) = self.momentum.take() {
+            self.current_position = DegreePosition::from_degrees(self.snap_angle(angle));
+        }
     }
     
     pub fn harmonic(&self) -> Harmonic {
@@ -65,6 +372,144 @@ impl Dial {
     
     pub fn set_harmonic(&mut self, harmonic: Harmonic) {
         self.harmonic = harmonic;
+        self.harmonic_tween = None;
+    }
+    
+    /// Changes the harmonic, letting the points glide to their new places on a harmonic face
+    pub fn switch_harmonic(&mut self, harmonic: Harmonic) {
+        if harmonic == self.harmonic || !self.harmonic_face || self.animation_speed <= 0.0 {
+            self.set_harmonic(harmonic);
+            return;
+        }
+        let from = self.face_factor();
+        self.harmonic = harmonic;
+        self.harmonic_tween = Some((from, 0.0));
+    }
+    
+    /// What longitudes are multiplied by on the face, between harmonics while one eases into another
+    fn face_factor(&self) -> f64 {
+        let to = self.harmonic.to_number() as f64;
+        match self.harmonic_tween {
+            // Eased on a log scale so a change from 1 to 1000 does not race past the low harmonics
+            Some((from, progress)) => from * (to / from).powf(ease(progress) as f64),
+            None => to,
+        }
+    }
+    
+    pub fn harmonic_face(&self) -> bool {
+        self.harmonic_face
+    }
+    
+    /// Shows the face as a harmonic dial, e.g. 0-90 on the 4th, or always as
+    /// the 360° zodiac with the harmonic's arms on the pointer
+    pub fn set_harmonic_face(&mut self, harmonic_face: bool) {
+        self.harmonic_face = harmonic_face;
+    }
+    
+    /// Degrees the face spans once round the ring
+    pub fn face_span(&self) -> f64 {
+        if self.harmonic_face {
+            360.0 / self.harmonic.to_number() as f64
+        } else {
+            360.0
+        }
+    }
+    
+    /// Returns the angle on the face, counterclockwise from the top, at which a longitude is drawn
+    pub fn face_angle(&self, longitude: f64) -> f32 {
+        let angle = if !self.harmonic_face {
+            longitude.rem_euclid(360.0)
+        } else if self.harmonic_tween.is_some() {
+            (longitude * self.face_factor()).rem_euclid(360.0)
+        } else {
+            calculate_harmonic_position(longitude, self.harmonic)
+        };
+        angle as f32
+    }
+    
+    /// Points of the first, outermost ring
+    pub fn points(&self) -> &[(PointId, DegreePosition)] {
+        self.rings.first().map_or(&[], |ring| ring.points.as_slice())
+    }
+    
+    /// Places bodies and personal points on the first ring, replacing any shown before
+    pub fn set_points(&mut self, points: Vec<(PointId, DegreePosition)>) {
+        match self.rings.first_mut() {
+            Some(ring) => ring.points = points,
+            None => self.rings.push(DialRing::new("", points, DEFAULT_RING_COLOR)),
+        }
+    }
+    
+    pub fn rings(&self) -> &[DialRing] {
+        &self.rings
+    }
+    
+    /// Shows one ring per chart, the first outermost, replacing any shown before
+    pub fn set_rings(&mut self, rings: Vec<DialRing>) {
+        self.rings = rings;
+    }
+    
+    /// Radius at which the markers of a ring sit
+    fn ring_radius(&self, ring: usize) -> f32 {
+        self.radius - ring as f32 * RING_SPACING
+    }
+    
+    /// Returns the ring and the point on it the red 0° arm points at on the face,
+    /// the nearest over all rings if several are in reach
+    pub fn pointed_at(&self) -> Option<(usize, PointId)> {
+        let arm = self.current_position.to_degrees();
+        let span = self.face_span();
+        self.rings
+            .iter()
+            .enumerate()
+            .flat_map(|(ring, dial_ring)| dial_ring.points.iter().map(move |point| (ring, point)))
+            .map(|(ring, (point, position))| {
+                let diff = (position.to_degrees() - arm).rem_euclid(span);
+                ((ring, *point), diff.min(span - diff))
+            })
+            .filter(|(_, distance)| *distance <= POINTER_TOLERANCE as f64)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(pointed, _)| pointed)
+    }
+    
+    pub fn houses(&self) -> Option<&Houses> {
+        self.houses.as_ref()
+    }
+    
+    /// Shows house cusps as an inner ring, or hides the ring with `None`
+    pub fn set_houses(&mut self, houses: Option<Houses>) {
+        self.houses = houses;
+    }
+    
+    pub fn aspect_lines(&self) -> &[(DegreePosition, DegreePosition, egui::Color32)] {
+        &self.aspect_lines
+    }
+    
+    /// Joins pairs of positions in aspect with lines across the middle of the dial
+    pub fn set_aspect_lines(&mut self, lines: Vec<(DegreePosition, DegreePosition, egui::Color32)>) {
+        self.aspect_lines = lines;
+    }
+    
+    pub fn mirror_axis(&self) -> bool {
+        self.mirror_axis
+    }
+    
+    /// Shows the red arm as a mirror axis, with each point of the first ring
+    /// reflected across it
+    pub fn set_mirror_axis(&mut self, mirror_axis: bool) {
+        self.mirror_axis = mirror_axis;
+    }
+    
+    /// Returns the points of the first ring reflected across the red arm
+    pub fn mirrored_points(&self) -> Vec<(PointId, DegreePosition)> {
+        let axis = self.current_position.to_degrees();
+        self.points()
+            .iter()
+            .map(|&(id, position)| {
+                let mirrored = reflect_across(position.to_degrees(), axis);
+                (id, DegreePosition::from_degrees(mirrored))
+            })
+            .collect()
     }
     
     pub fn update_modifiers(&mut self, modifiers: egui::Modifiers) {
@@ -91,27 +536,60 @@ impl Dial {
     
     /// Handle keyboard input
     pub fn handle_key(&mut self, key: egui::Key) {
-        let increment = self.get_increment();
+        // Stepped in whole seconds, so no step is lost however far round the dial
+        let increment = DegreePosition::from_degrees(self.get_increment());
+        // Steps count from the end of any jump under way, or from where a
+        // coasting dial comes to rest
+        self.stop_momentum();
+        let target = self.target_position();
         
         match key {
             egui::Key::ArrowLeft => {
                 // Counterclockwise movement (increasing angle)
-                let new_degrees = self.current_position.to_degrees() + increment;
-                self.current_position = DegreePosition::from_degrees(new_degrees);
+                self.set_position(target.add(increment));
             }
             egui::Key::ArrowRight => {
                 // Clockwise movement (decreasing angle)
-                let new_degrees = self.current_position.to_degrees() - increment;
-                self.current_position = DegreePosition::from_degrees(new_degrees);
+                self.set_position(target.subtract(increment));
             }
+            // Sign-sized jumps and the return to 0° are animated
+            egui::Key::PageUp => self.go_to(target.add(PAGE_JUMP), true),
+            egui::Key::PageDown => self.go_to(target.subtract(PAGE_JUMP), true),
+            egui::Key::Home => self.go_to(DegreePosition::new(0, 0, 0), true),
             _ => {}
         }
-    }    
+    }
+    
+    /// Turns the dial by a mouse wheel or trackpad scroll, a step of the increment
+    /// for the modifiers held per line scrolled. Away from you turns it counterclockwise.
+    pub fn handle_scroll(&mut self, unit: egui::MouseWheelUnit, delta: egui::Vec2, modifiers: egui::Modifiers) {
+        // Horizontal scrolling counts too, as Shift turns the wheel sideways on some systems
+        let amount = (delta.x + delta.y) as f64;
+        self.scroll_remainder += match unit {
+            egui::MouseWheelUnit::Point => amount / SCROLL_POINTS_PER_STEP,
+            egui::MouseWheelUnit::Line | egui::MouseWheelUnit::Page => amount,
+        };
+        let steps = self.scroll_remainder.trunc();
+        if steps == 0.0 {
+            return;
+        }
+        self.scroll_remainder -= steps;
+        
+        self.update_modifiers(modifiers);
+        let increment = DegreePosition::from_degrees(self.get_increment()).arcseconds() as i64;
+        let turn = DegreePosition::from_arcseconds(increment * steps as i64);
+        self.stop_momentum();
+        self.set_position(self.target_position().add(turn));
+    }
+
     /// Handle mouse drag - improved to keep position when clicking and fix direction
     pub fn handle_pointer_interaction(&mut self, pointer_pos: Option<egui::Pos2>, drag_started: bool, drag_released: bool) {
         if drag_started {
             // When drag starts, store the starting position and current angle
             self.is_dragging = true;
+            self.animation = None;
+            self.momentum = None;
+            self.drag_velocity = 0.0;
             self.drag_start_pos = pointer_pos;
             self.drag_start_angle = self.current_position.to_degrees();
         }
@@ -143,15 +621,17 @@ impl Dial {
                     
                     // Calculate the new position based on the start position and the angle difference
                     // FIXED: Changed to addition to match expected direction
-                    let new_angle = self.drag_start_angle + angle_diff;
+                    // On a harmonic face the ring turns n times as fast as the zodiac
+                    let new_angle = self.drag_start_angle + angle_diff as f64 * self.face_span() / 360.0;
                     
                     // Snap to increment
                     let snapped_angle = self.snap_angle(new_angle);
                     self.current_position = DegreePosition::from_degrees(snapped_angle);
                     
-                    // Update the drag start position and angle for the next frame
+                    // Update the drag start position and angle for the next frame. The
+                    // unsnapped angle is kept so slow drags on a harmonic face add up.
                     self.drag_start_pos = Some(current_pos);
-                    self.drag_start_angle = snapped_angle;
+                    self.drag_start_angle = new_angle;
                 }
             }
         }
@@ -161,22 +641,77 @@ impl Dial {
     pub fn ui(&mut self, ui: &mut egui::Ui) -> egui::Response {
         // Create a custom widget area for the dial
         let (response, painter) = ui.allocate_painter(
-            egui::Vec2::new(800.0, 450.0),
+            egui::Vec2::new(800.0, 500.0),
             egui::Sense::click_and_drag(),
         );
         
         self.center = response.rect.center();
         
-        // Handle mouse interaction
+        let dt = ui.input(|i| i.stable_dt).min(MAX_FRAME_SECONDS);
+        if self.is_animating() {
+            self.advance_animation(dt);
+            ui.ctx().request_repaint();
+        }
+        
+        // Handle mouse interaction, following the speed of a drag in case it ends in a flick
+        let was_dragging = self.is_dragging;
+        let dragged_from = self.drag_start_angle;
         self.handle_pointer_interaction(
             response.interact_pointer_pos(),
             response.drag_started(),
             response.drag_released(),
         );
+        if self.is_dragging {
+            if was_dragging && dt > 0.0 {
+                let velocity = (self.drag_start_angle - dragged_from) / dt as f64;
+                self.drag_velocity = (self.drag_velocity + velocity) / 2.0;
+            }
+            // Keep measuring while the pointer is held still, so a pause before letting go stops the dial
+            ui.ctx().request_repaint();
+        } else if was_dragging {
+            self.start_momentum();
+            ui.ctx().request_repaint();
+        }
+        
+        // The raw wheel events, as egui turns Ctrl+scroll into zoom and Shift+scroll sideways
+        if response.hovered() {
+            let wheel: Vec<_> = ui.input(|i| {
+                i.events
+                    .iter()
+                    .filter_map(|event| match event {
+                        egui::Event::MouseWheel { unit, delta, modifiers } => Some((*unit, *delta, *modifiers)),
+                        _ => None,
+                    })
+                    .collect()
+            });
+            for (unit, delta, modifiers) in wheel {
+                self.handle_scroll(unit, delta, modifiers);
+            }
+        }
         
         // Draw the dial
         self.draw(&painter);
         
+        // Show the exact position of the glyph under the pointer
+        if let Some(pointer) = response.hover_pos() {
+            let hovered = self
+                .layout_points()
+                .into_iter()
+                .map(|placed| (placed.glyph.distance(pointer), placed))
+                .filter(|(distance, _)| *distance <= GLYPH_HOVER_RADIUS)
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            if let Some((_, placed)) = hovered {
+                egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("dial_point_tooltip"), |ui| {
+                    if self.rings.len() > 1 {
+                        ui.label(&self.rings[placed.ring].label);
+                    }
+                    ui.strong(placed.id.name());
+                    ui.monospace(placed.position.format());
+                    ui.label(format_in_sign(placed.position.to_degrees()));
+                });
+            }
+        }
+        
         response
     }    
     /// Draw the dial
@@ -191,44 +726,61 @@ impl Dial {
         // Draw reversed outer numbering (clockwise)
         self.draw_outer_degree_markings(painter);
         
+        // Draw the house ring, aspects and chart points against the fixed zodiac
+        self.draw_house_ring(painter);
+        self.draw_aspect_lines(painter);
+        self.draw_points(painter);
+        
         // Draw inner tick marks
         self.draw_inner_tick_marks(painter);
         
-        // Draw the 4 main arms
+        // Draw the 4 main arms, and the mirror axis along the red one
         self.draw_main_arms(painter);
+        if self.mirror_axis {
+            self.draw_mirror_axis(painter);
+        }
         
         // Draw center dot
         painter.circle_filled(self.center, 4.0, egui::Color32::from_gray(60));
     }
     
-    /// Draw reversed outer degree markings (clockwise)
+    /// Draw the face's degree scale, with tick spacing adapted to its span
     fn draw_outer_degree_markings(&self, painter: &egui::Painter) {
-        // Draw tick marks and numbers (clockwise)
-        for i in 0..360 {
-            // Calculate angle for clockwise numbering
-            let display_degree = i as f32;
-            let angle = (90.0 + display_degree) * PI / 180.0; // 90° offset for 0° at top, + for clockwise
-            
-            // Outer tick radius
-            let outer_radius = self.radius + 5.0;
+        let span = self.face_span();
+        let span_arcseconds = span * 3600.0;
+        let (minor, medium, major) = tick_steps(span);
+        
+        let outer_radius = self.radius + 5.0;
+        let mut value = 0u32;
+        while (value as f64) < span_arcseconds - 1e-6 {
+            // 0 at the top, increasing counterclockwise like the zodiac
+            let angle = (90.0 + (value as f64 / span_arcseconds * 360.0) as f32) * PI / 180.0;
+            let (inner_radius, width, gray) = if value.is_multiple_of(major) {
+                (self.radius + 15.0, 2.0, 80)
+            } else if value.is_multiple_of(medium) {
+                (self.radius + 10.0, 1.0, 120)
+            } else {
+                (self.radius + 7.0, 0.5, 150)
+            };
+            let start = egui::pos2(
+                self.center.x + outer_radius * angle.cos(),
+                self.center.y - outer_radius * angle.sin(),
+            );
+            let end = egui::pos2(
+                self.center.x + inner_radius * angle.cos(),
+                self.center.y - inner_radius * angle.sin(),
+            );
+            painter.line_segment(
+                [start, end],
+                egui::Stroke::new(width, egui::Color32::from_gray(gray)),
+            );
             
-            if i % 30 == 0 {
-                // Major tick
-                let inner_radius = self.radius + 15.0;
-                let start = egui::pos2(
-                    self.center.x + outer_radius * angle.cos(),
-                    self.center.y - outer_radius * angle.sin(),
-                );
-                let end = egui::pos2(
-                    self.center.x + inner_radius * angle.cos(),
-                    self.center.y - inner_radius * angle.sin(),
-                );
-                painter.line_segment(
-                    [start, end],
-                    egui::Stroke::new(2.0, egui::Color32::from_gray(80)),
-                );
-                
-                // Draw degree label
+            if value.is_multiple_of(major) {
+                let label = if major.is_multiple_of(3600) {
+                    format!("{}", value / 3600)
+                } else {
+                    DegreePosition::from_arcseconds(value as i64).format()
+                };
                 let label_radius = self.radius + 30.0;
                 let label_pos = egui::pos2(
                     self.center.x + label_radius * angle.cos(),
@@ -237,47 +789,249 @@ impl Dial {
                 painter.text(
                     label_pos,
                     egui::Align2::CENTER_CENTER,
-                    format!("{}", i),
+                    label,
                     egui::FontId::default(),
                     egui::Color32::from_gray(60),
-                );            } else if i % 5 == 0 {
-                // Medium tick every 5 degrees
-                let inner_radius = self.radius + 10.0;
-                let start = egui::pos2(
-                    self.center.x + outer_radius * angle.cos(),
-                    self.center.y - outer_radius * angle.sin(),
-                );
-                let end = egui::pos2(
-                    self.center.x + inner_radius * angle.cos(),
-                    self.center.y - inner_radius * angle.sin(),
-                );
-                painter.line_segment(
-                    [start, end],
-                    egui::Stroke::new(1.0, egui::Color32::from_gray(120)),
                 );
+            }
+            value += minor;
+        }
+        
+        if self.harmonic_face && self.harmonic == Harmonic::Fourth {
+            self.draw_modality_sectors(painter);
+        }
+    }
+    
+    /// Draw the cardinal, fixed and mutable thirds of the 90° dial outside the scale
+    fn draw_modality_sectors(&self, painter: &egui::Painter) {
+        let radius = self.radius + 43.0;
+        let sectors = [
+            ("Cardinal", egui::Color32::from_rgb(200, 80, 60)),
+            ("Fixed", egui::Color32::from_rgb(70, 120, 190)),
+            ("Mutable", egui::Color32::from_rgb(90, 160, 90)),
+        ];
+        for (i, (name, color)) in sectors.into_iter().enumerate() {
+            // Each 30° of the 90° dial fills a third of the ring
+            let start = 90.0 + 120.0 * i as f32;
+            let arc: Vec<egui::Pos2> = (0..=60)
+                .map(|step| {
+                    let angle = (start + step as f32 * 2.0) * PI / 180.0;
+                    egui::pos2(
+                        self.center.x + radius * angle.cos(),
+                        self.center.y - radius * angle.sin(),
+                    )
+                })
+                .collect();
+            painter.add(egui::Shape::line(arc, egui::Stroke::new(5.0, color.linear_multiply(0.5))));
+            
+            let middle = (start + 60.0) * PI / 180.0;
+            let label_radius = radius + 10.0;
+            painter.text(
+                egui::pos2(
+                    self.center.x + label_radius * middle.cos(),
+                    self.center.y - label_radius * middle.sin(),
+                ),
+                egui::Align2::CENTER_CENTER,
+                name,
+                egui::FontId::proportional(11.0),
+                color,
+            );
+        }
+    }
+    
+    /// Draw house cusps as a ring inside the dial
+    fn draw_house_ring(&self, painter: &egui::Painter) {
+        let Some(houses) = &self.houses else {
+            return;
+        };
+        
+        // Inside the innermost chart ring
+        let outer_radius = self.ring_radius(self.rings.len().max(1) - 1) - 40.0;
+        let inner_radius = outer_radius - 20.0;
+        let color = egui::Color32::from_rgb(150, 90, 40);
+        painter.circle_stroke(self.center, outer_radius, egui::Stroke::new(1.0, color));
+        painter.circle_stroke(self.center, inner_radius, egui::Stroke::new(1.0, color));
+        
+        for (i, &cusp) in houses.cusps().iter().enumerate() {
+            let angle = (90.0 + self.face_angle(cusp)) * PI / 180.0;
+            let start = egui::pos2(
+                self.center.x + outer_radius * angle.cos(),
+                self.center.y - outer_radius * angle.sin(),
+            );
+            let end = egui::pos2(
+                self.center.x + inner_radius * angle.cos(),
+                self.center.y - inner_radius * angle.sin(),
+            );
+            painter.line_segment([start, end], egui::Stroke::new(1.5, color));
+            
+            // Number each house halfway to the next cusp. Houses overlap on a
+            // harmonic face, so there the number sits beside its cusp instead.
+            let label_angle = if self.face_span() < 360.0 {
+                angle + 4.0 * PI / 180.0
             } else {
-                // Minor tick for every degree
-                let inner_radius = self.radius + 7.0;
-                let start = egui::pos2(
-                    self.center.x + outer_radius * angle.cos(),
-                    self.center.y - outer_radius * angle.sin(),
-                );
-                let end = egui::pos2(
-                    self.center.x + inner_radius * angle.cos(),
-                    self.center.y - inner_radius * angle.sin(),
-                );
-                painter.line_segment(
-                    [start, end],
-                    egui::Stroke::new(0.5, egui::Color32::from_gray(150)),
-                );
+                let next = houses.cusps()[(i + 1) % 12];
+                let middle = cusp + (next - cusp).rem_euclid(360.0) / 2.0;
+                (90.0 + middle as f32) * PI / 180.0
+            };
+            let label_radius = (outer_radius + inner_radius) / 2.0;
+            painter.text(
+                egui::pos2(
+                    self.center.x + label_radius * label_angle.cos(),
+                    self.center.y - label_radius * label_angle.sin(),
+                ),
+                egui::Align2::CENTER_CENTER,
+                format!("{}", i + 1),
+                egui::FontId::proportional(10.0),
+                color,
+            );
+        }
+    }
+    
+    /// Draw aspect lines as chords inside the innermost ring and the house ring
+    fn draw_aspect_lines(&self, painter: &egui::Painter) {
+        let mut radius = self.ring_radius(self.rings.len().max(1) - 1) - 40.0;
+        if self.houses.is_some() {
+            radius -= 25.0;
+        }
+        let on_circle = |position: DegreePosition| {
+            let angle = (90.0 + self.face_angle(position.to_degrees())) * PI / 180.0;
+            egui::pos2(
+                self.center.x + radius * angle.cos(),
+                self.center.y - radius * angle.sin(),
+            )
+        };
+        
+        for &(first, second, color) in &self.aspect_lines {
+            // Points in aspect can coincide on a harmonic face, leaving nothing to draw
+            let (start, end) = (on_circle(first), on_circle(second));
+            if start.distance(end) > 1.0 {
+                painter.line_segment([start, end], egui::Stroke::new(1.0, color));
+            } else {
+                painter.circle_stroke(start, 4.0, egui::Stroke::new(1.0, color));
+            }
+        }
+    }
+    
+    /// Places each point of every ring on the face, fanning out glyphs that would overlap
+    fn layout_points(&self) -> Vec<PlacedPoint> {
+        let on_ring = |angle: f32, radius: f32| {
+            // Same orientation as the outer numbering, so the red arm points at the point
+            let angle = (90.0 + angle) * PI / 180.0;
+            egui::pos2(
+                self.center.x + radius * angle.cos(),
+                self.center.y - radius * angle.sin(),
+            )
+        };
+        
+        let mut placed = Vec::new();
+        for (ring, dial_ring) in self.rings.iter().enumerate() {
+            let angles: Vec<f32> = dial_ring
+                .points
+                .iter()
+                .map(|(_, position)| self.face_angle(position.to_degrees()))
+                .collect();
+            let spread = spread_angles(&angles, GLYPH_SEPARATION * ring_scale(ring));
+            let radius = self.ring_radius(ring);
+            placed.extend(
+                dial_ring
+                    .points
+                    .iter()
+                    .zip(angles.iter().zip(&spread))
+                    .map(|(&(id, position), (&exact, &glyph))| PlacedPoint {
+                        ring,
+                        id,
+                        position,
+                        exact: on_ring(exact, radius),
+                        glyph: on_ring(glyph, radius - 25.0),
+                    }),
+            );
+        }
+        placed
+    }
+    
+    /// Draw point glyphs at their longitudes on each ring, reduced to the face,
+    /// with leader lines from glyphs that had to move aside
+    fn draw_points(&self, painter: &egui::Painter) {
+        let glyph_font = egui::FontId::proportional(16.0);
+        let abbreviation_font = egui::FontId::monospace(11.0);
+        
+        // Outline the inner rings so their markers have a line to sit on
+        for (ring, dial_ring) in self.rings.iter().enumerate().skip(1) {
+            painter.circle_stroke(
+                self.center,
+                self.ring_radius(ring),
+                egui::Stroke::new(1.0, dial_ring.color.linear_multiply(0.4)),
+            );
+        }
+        
+        for placed in self.layout_points() {
+            let color = self.rings[placed.ring].color;
+            // Transneptunian points are told apart from the real planets by a hollow marker
+            if placed.id.is_transneptunian() {
+                painter.circle_stroke(placed.exact, 3.0, egui::Stroke::new(1.0, color));
+            } else {
+                painter.circle_filled(placed.exact, 3.0, color);
+            }
+            
+            // Lead from the exact degree to the glyph, stopping short of both
+            let towards_centre = (self.center - placed.exact).normalized();
+            let leader_start = placed.exact + towards_centre * 5.0;
+            let leader_end = placed.glyph + (leader_start - placed.glyph).normalized() * 9.0;
+            if (leader_end - leader_start).dot(towards_centre) > 0.0 {
+                painter.line_segment([leader_start, leader_end], egui::Stroke::new(0.5, color));
             }
+            
+            // Fall back to the abbreviation where there is no symbol or the font lacks it
+            let glyph = placed
+                .id
+                .glyph()
+                .filter(|glyph| painter.ctx().fonts(|fonts| fonts.has_glyphs(&glyph_font, glyph)));
+            let (text, font) = match glyph {
+                Some(glyph) => (glyph, glyph_font.clone()),
+                None => (placed.id.abbreviation(), abbreviation_font.clone()),
+            };
+            painter.text(placed.glyph, egui::Align2::CENTER_CENTER, text, font, color);
+        }
+    }
+    
+    /// Draw the mirror axis through the red arm and the reflected points of the first ring
+    fn draw_mirror_axis(&self, painter: &egui::Painter) {
+        let color = egui::Color32::from_rgb(150, 60, 170);
+        let on_circle = |angle: f32, radius: f32| {
+            let angle = (90.0 + angle) * PI / 180.0;
+            egui::pos2(
+                self.center.x + radius * angle.cos(),
+                self.center.y - radius * angle.sin(),
+            )
+        };
+        
+        let axis = self.face_angle(self.current_position.to_degrees());
+        let reach = self.radius - 15.0;
+        painter.add(egui::Shape::dashed_line(
+            &[on_circle(axis, reach), on_circle(axis + 180.0, reach)],
+            egui::Stroke::new(1.0, color),
+            6.0,
+            4.0,
+        ));
+        
+        let radius = self.ring_radius(0) - 12.0;
+        for (id, position) in self.mirrored_points() {
+            let marker = on_circle(self.face_angle(position.to_degrees()), radius);
+            painter.circle_stroke(marker, 3.0, egui::Stroke::new(1.0, color));
+            painter.text(
+                marker + (self.center - marker).normalized() * 10.0,
+                egui::Align2::CENTER_CENTER,
+                id.abbreviation(),
+                egui::FontId::monospace(8.0),
+                color,
+            );
         }
     }
     
     /// Draw inner tick marks
     fn draw_inner_tick_marks(&self, painter: &egui::Painter) {
         // Get current rotation angle - FIXED: Now uses negative to match clockwise direction
-        let current_degree = -self.current_position.to_degrees();
+        let current_degree = -self.face_angle(self.current_position.to_degrees());
         
         // Draw tick marks inside the dial circle
         for i in 0..360 {
@@ -345,7 +1099,7 @@ impl Dial {
     /// Draw the 4 main arms
     fn draw_main_arms(&self, painter: &egui::Painter) {
         // Get current rotation angle - FIXED: Now uses negative to match clockwise direction
-        let current_degree = -self.current_position.to_degrees();
+        let current_degree = -self.face_angle(self.current_position.to_degrees());
         
         // Draw the 4 main cardinal arms (at 0, 90, 180, 270 degrees)
         for i in 0..4 {
@@ -376,41 +1130,54 @@ impl Dial {
             );
         }
         
-        // Draw additional arms based on harmonic (thinner)
-        let arm_count = match self.harmonic {
-            Harmonic::First => 4,
-            Harmonic::Second => 8,
-            Harmonic::Fourth => 16,
-            Harmonic::Eighth => 32,
-            Harmonic::Sixteenth => 64,
-            _ => 4,
-        };
+        // Draw additional arms based on harmonic (thinner). A harmonic face folds
+        // them onto the main arms, and past a limit they would merge into a
+        // solid disc, so then only the main arms remain.
+        let arm_count = self.harmonic.arm_count();
+        if self.harmonic_face || arm_count <= 4 || arm_count > MAX_DRAWN_ARMS {
+            return;
+        }
+        let main_step = arm_count / 4;
+        let span_step = arm_count / self.harmonic.to_number();
+        let label_spans = self.harmonic.to_number() <= MAX_LABELLED_SPANS;
         
-        if arm_count > 4 {
-            let base_angle_step = 360.0 / arm_count as f32;
+        for (i, offset) in self.harmonic.arm_angles().enumerate() {
+            let i = i as u32;
+            // Skip the main arms (0, 90, 180, 270)
+            if i.is_multiple_of(main_step) {
+                continue;
+            }
+            // Calculate angle for the arm, considering the current position
+            let arm_angle = (offset as f32 + current_degree) % 360.0;
+            let angle_rad = (90.0 - arm_angle) * PI / 180.0;
             
-            for i in 0..arm_count {
-                // Skip the main arms (0, 90, 180, 270)
-                if i % (arm_count / 4) == 0 {
-                    continue;
-                }                
-                // Calculate angle for the arm, considering the current position
-                let arm_angle = (base_angle_step * i as f32 + current_degree) % 360.0;
-                let angle_rad = (90.0 - arm_angle) * PI / 180.0;
-                
-                let inner_radius = 30.0;
-                let start = egui::pos2(
-                    self.center.x + inner_radius * angle_rad.cos(),
-                    self.center.y - inner_radius * angle_rad.sin(),
-                );
-                let end = egui::pos2(
-                    self.center.x + (self.radius - 20.0) * angle_rad.cos(),
-                    self.center.y - (self.radius - 20.0) * angle_rad.sin(),
-                );
-                
-                painter.line_segment(
-                    [start, end],
-                    egui::Stroke::new(0.5, egui::Color32::from_gray(140)),
+            let inner_radius = 30.0;
+            let start = egui::pos2(
+                self.center.x + inner_radius * angle_rad.cos(),
+                self.center.y - inner_radius * angle_rad.sin(),
+            );
+            let end = egui::pos2(
+                self.center.x + (self.radius - 20.0) * angle_rad.cos(),
+                self.center.y - (self.radius - 20.0) * angle_rad.sin(),
+            );
+            
+            painter.line_segment(
+                [start, end],
+                egui::Stroke::new(0.5, egui::Color32::from_gray(140)),
+            );
+            
+            // Label the arms a whole harmonic span from the red arm, e.g. 72° on the 5th
+            if label_spans && i.is_multiple_of(span_step) {
+                let label_radius = inner_radius + 15.0;
+                painter.text(
+                    egui::pos2(
+                        self.center.x + label_radius * angle_rad.cos(),
+                        self.center.y - label_radius * angle_rad.sin(),
+                    ),
+                    egui::Align2::CENTER_CENTER,
+                    DegreePosition::from_degrees(offset).format(),
+                    egui::FontId::proportional(9.0),
+                    egui::Color32::from_gray(110),
                 );
             }
         }
@@ -420,6 +1187,7 @@ impl Dial {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::astrology::Body;
     
     #[test]
     fn test_increment_calculation() {
@@ -440,6 +1208,374 @@ mod tests {
         dial.ctrl_pressed = true;
         assert_eq!(dial.get_increment(), 1.0 / 3600.0);
     }
+
+    #[test]
+    fn test_go_to() {
+        let mut dial = Dial::new();
+        dial.go_to(DegreePosition::new(123, 45, 12), false);
+        assert_eq!(dial.position(), DegreePosition::new(123, 45, 12));
+        assert!(!dial.is_animating());
+
+        // Animated across 0° the short way, landing exactly on the target
+        dial.set_position(DegreePosition::new(350, 0, 0));
+        let target = DegreePosition::new(10, 0, 1);
+        dial.go_to(target, true);
+        assert_eq!(dial.position(), DegreePosition::new(350, 0, 0));
+        assert_eq!(dial.target_position(), target);
+        // Frames are stepped a quarter of the way at a time
+        let quarter = GO_TO_SECONDS / 4.0;
+        dial.advance_animation(quarter);
+        dial.advance_animation(quarter);
+        let halfway = dial.position().to_degrees();
+        assert!((halfway - 0.0).abs() < 0.01 || (halfway - 360.0).abs() < 0.01, "{}", halfway);
+        dial.advance_animation(quarter);
+        dial.advance_animation(quarter);
+        assert!(!dial.is_animating());
+        assert_eq!(dial.position(), target);
+
+        // On the 90° face the dial turns at most 45°, then shows the exact target
+        dial.set_harmonic(Harmonic::Fourth);
+        dial.go_to(DegreePosition::new(190, 0, 0), true);
+        dial.advance_animation(quarter);
+        dial.advance_animation(quarter);
+        assert!((dial.position().to_degrees() - 10.0).abs() < 0.01);
+        dial.advance_animation(quarter);
+        dial.advance_animation(quarter);
+        assert_eq!(dial.position(), DegreePosition::new(190, 0, 0));
+
+        // A key press finishes the jump before stepping
+        dial.go_to(DegreePosition::new(200, 0, 0), true);
+        dial.shift_pressed = true;
+        dial.handle_key(egui::Key::ArrowLeft);
+        assert_eq!(dial.position(), DegreePosition::new(201, 0, 0));
+        assert!(!dial.is_animating());
+    }
+
+    #[test]
+    fn test_harmonic_tween() {
+        let mut dial = Dial::new();
+        dial.set_harmonic(Harmonic::Fourth);
+        dial.switch_harmonic(Harmonic::Eighth);
+        assert_eq!(dial.harmonic(), Harmonic::Eighth);
+        assert!(dial.is_animating());
+        // The points start where they were and glide to their new places
+        assert_eq!(dial.face_angle(10.0), 40.0);
+        dial.advance_animation(MAX_FRAME_SECONDS);
+        let between = dial.face_angle(10.0);
+        assert!(between > 40.0 && between < 80.0, "{}", between);
+        for _ in 0..10 {
+            dial.advance_animation(MAX_FRAME_SECONDS);
+        }
+        assert!(!dial.is_animating());
+        assert_eq!(dial.face_angle(10.0), 80.0);
+
+        // Twice as fast takes half the frames
+        dial.set_animation_speed(2.0);
+        dial.switch_harmonic(Harmonic::Fourth);
+        for _ in 0..4 {
+            dial.advance_animation(MAX_FRAME_SECONDS);
+        }
+        assert!(!dial.is_animating());
+
+        // With the animations off, or on the zodiac face, the change is immediate
+        dial.set_animation_speed(0.0);
+        dial.switch_harmonic(Harmonic::First);
+        assert!(!dial.is_animating());
+        dial.set_animation_speed(1.0);
+        dial.set_harmonic_face(false);
+        dial.switch_harmonic(Harmonic::Fourth);
+        assert!(!dial.is_animating());
+        dial.go_to(DegreePosition::new(10, 0, 0), true);
+        assert!(dial.is_animating());
+        dial.set_animation_speed(0.0);
+        dial.go_to(DegreePosition::new(20, 0, 0), true);
+        assert!(!dial.is_animating());
+    }
+
+    #[test]
+    fn test_momentum() {
+        let mut dial = Dial::new();
+        dial.drag_start_angle = 10.0;
+        dial.drag_velocity = 90.0;
+        dial.start_momentum();
+        assert!(dial.is_animating());
+        let mut last = 10.0;
+        for _ in 0..100 {
+            dial.advance_animation(MAX_FRAME_SECONDS / 2.0);
+            let position = dial.position().to_degrees();
+            assert!(position >= last);
+            last = position;
+        }
+        // Coasts about speed / friction before stopping on a whole minute
+        assert!(!dial.is_animating());
+        assert!((last - 10.0 - 90.0 / FRICTION).abs() < 1.0, "{}", last);
+        assert_eq!(dial.position().arcseconds() % 60, 0);
+
+        // A slow release or inertia turned off leaves the dial where it is
+        dial.drag_velocity = 10.0;
+        dial.start_momentum();
+        assert!(!dial.is_animating());
+        dial.set_inertia(false);
+        dial.drag_velocity = 90.0;
+        dial.start_momentum();
+        assert!(!dial.is_animating());
+
+        // Turning inertia off mid-coast snaps to the current increment
+        dial.set_inertia(true);
+        dial.shift_pressed = true;
+        dial.drag_start_angle = 100.0;
+        dial.drag_velocity = -200.0;
+        dial.start_momentum();
+        dial.advance_animation(MAX_FRAME_SECONDS);
+        dial.set_inertia(false);
+        assert!(!dial.is_animating());
+        assert_eq!(dial.position().arcseconds() % 3600, 0);
+    }
+
+    #[test]
+    fn test_step_while_coasting() {
+        // A key or wheel step stops a coasting dial on a step before moving it on
+        let mut dial = Dial::new();
+        for step in 0..2 {
+            dial.drag_start_angle = 10.0;
+            dial.drag_velocity = 90.0;
+            dial.start_momentum();
+            dial.advance_animation(MAX_FRAME_SECONDS);
+            assert!(dial.is_animating());
+            assert_ne!(dial.position().arcseconds() % 60, 0);
+            if step == 0 {
+                dial.handle_key(egui::Key::ArrowLeft);
+            } else {
+                dial.handle_scroll(egui::MouseWheelUnit::Line, egui::vec2(0.0, 1.0), egui::Modifiers::NONE);
+            }
+            assert!(!dial.is_animating());
+            assert_eq!(dial.position().arcseconds() % 60, 0);
+        }
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut dial = Dial::new();
+        let (line, point) = (egui::MouseWheelUnit::Line, egui::MouseWheelUnit::Point);
+
+        // A notch of the wheel is a step of the increment for the modifiers held
+        dial.handle_scroll(line, egui::vec2(0.0, 1.0), egui::Modifiers::NONE);
+        assert_eq!(dial.position(), DegreePosition::new(0, 1, 0));
+        dial.handle_scroll(line, egui::vec2(0.0, 2.0), egui::Modifiers::SHIFT);
+        assert_eq!(dial.position(), DegreePosition::new(2, 1, 0));
+        let fine = egui::Modifiers { shift: true, ctrl: true, ..Default::default() };
+        dial.handle_scroll(line, egui::vec2(0.0, -3.0), fine);
+        assert_eq!(dial.position(), DegreePosition::new(2, 0, 57));
+
+        // Shift+scroll arriving sideways still turns the dial
+        dial.handle_scroll(line, egui::vec2(-1.0, 0.0), egui::Modifiers::SHIFT);
+        assert_eq!(dial.position(), DegreePosition::new(1, 0, 57));
+
+        // A trackpad adds up small scrolls until they make a step
+        dial.set_position(DegreePosition::new(0, 0, 0));
+        for _ in 0..4 {
+            dial.handle_scroll(point, egui::vec2(0.0, -20.0), egui::Modifiers::SHIFT);
+        }
+        assert_eq!(dial.position(), DegreePosition::new(359, 0, 0));
+        dial.handle_scroll(point, egui::vec2(0.0, -20.0), egui::Modifiers::SHIFT);
+        assert_eq!(dial.position(), DegreePosition::new(358, 0, 0));
+    }
+
+    #[test]
+    fn test_page_keys() {
+        let mut dial = Dial::new();
+        dial.set_animation_speed(0.0);
+        dial.handle_key(egui::Key::PageUp);
+        dial.handle_key(egui::Key::PageUp);
+        assert_eq!(dial.position(), DegreePosition::new(60, 0, 0));
+        dial.set_position(DegreePosition::new(10, 20, 30));
+        dial.handle_key(egui::Key::PageDown);
+        assert_eq!(dial.position(), DegreePosition::new(340, 20, 30));
+        dial.handle_key(egui::Key::Home);
+        assert_eq!(dial.position(), DegreePosition::new(0, 0, 0));
+
+        // Animated, a second press carries on from where the first was heading
+        dial.set_animation_speed(1.0);
+        dial.handle_key(egui::Key::PageUp);
+        dial.advance_animation(MAX_FRAME_SECONDS);
+        dial.handle_key(egui::Key::PageUp);
+        assert_eq!(dial.target_position(), DegreePosition::new(60, 0, 0));
+        for _ in 0..5 {
+            dial.advance_animation(MAX_FRAME_SECONDS);
+        }
+        assert_eq!(dial.position(), DegreePosition::new(60, 0, 0));
+    }
+
+    #[test]
+    fn test_second_steps() {
+        // Shift+Ctrl steps one second at a time even near 360°
+        let mut dial = Dial::new();
+        dial.shift_pressed = true;
+        dial.ctrl_pressed = true;
+        dial.set_position(DegreePosition::new(359, 59, 58));
+        dial.handle_key(egui::Key::ArrowLeft);
+        assert_eq!(dial.position(), DegreePosition::new(359, 59, 59));
+        dial.handle_key(egui::Key::ArrowLeft);
+        assert_eq!(dial.position(), DegreePosition::new(0, 0, 0));
+        dial.handle_key(egui::Key::ArrowRight);
+        dial.handle_key(egui::Key::ArrowRight);
+        assert_eq!(dial.position(), DegreePosition::new(359, 59, 58));
+    
+        for _ in 0..3600 {
+            dial.handle_key(egui::Key::ArrowLeft);
+        }
+        assert_eq!(dial.position(), DegreePosition::new(0, 59, 58));
+    }
+    
+    #[test]
+    fn test_set_points() {
+        let mut dial = Dial::new();
+        assert!(dial.points().is_empty());
+        
+        dial.set_points(vec![
+            (PointId::Body(Body::Sun), DegreePosition::from_degrees(280.0)),
+            (PointId::Body(Body::Kronos), DegreePosition::from_degrees(87.5)),
+            (PointId::Midheaven, DegreePosition::from_degrees(270.0)),
+        ]);
+        assert_eq!(dial.points().len(), 3);
+        assert_eq!(dial.points()[1].0, PointId::Body(Body::Kronos));
+    }
+    
+    #[test]
+    fn test_pointed_at() {
+        let mut dial = Dial::new();
+        dial.set_points(vec![
+            (PointId::Body(Body::Sun), DegreePosition::from_degrees(359.5)),
+            (PointId::Body(Body::Moon), DegreePosition::from_degrees(120.0)),
+            (PointId::Body(Body::Mars), DegreePosition::from_degrees(120.5)),
+        ]);
+        assert_eq!(dial.pointed_at(), Some((0, PointId::Body(Body::Sun))));
+        
+        dial.set_position(DegreePosition::from_degrees(120.4));
+        assert_eq!(dial.pointed_at(), Some((0, PointId::Body(Body::Mars))));
+        
+        dial.set_position(DegreePosition::from_degrees(200.0));
+        assert_eq!(dial.pointed_at(), None);
+    }
+    
+    #[test]
+    fn test_pointed_at_outer_rings() {
+        let mut dial = Dial::new();
+        let ring = |points: Vec<(Body, f64)>| {
+            let points = points
+                .into_iter()
+                .map(|(body, longitude)| (PointId::Body(body), DegreePosition::from_degrees(longitude)))
+                .collect();
+            DialRing::new("", points, DEFAULT_RING_COLOR)
+        };
+        dial.set_rings(vec![
+            ring(vec![(Body::Sun, 10.0)]),
+            ring(vec![(Body::Sun, 60.0)]),
+            ring(vec![(Body::Saturn, 200.2), (Body::Moon, 201.0)]),
+        ]);
+        dial.set_position(DegreePosition::from_degrees(200.0));
+        assert_eq!(dial.pointed_at(), Some((2, PointId::Body(Body::Saturn))));
+        dial.set_position(DegreePosition::from_degrees(60.0));
+        assert_eq!(dial.pointed_at(), Some((1, PointId::Body(Body::Sun))));
+    }
+    
+    #[test]
+    fn test_rings() {
+        let mut dial = Dial::new();
+        let sun = (PointId::Body(Body::Sun), DegreePosition::from_degrees(100.0));
+        let moon = (PointId::Body(Body::Moon), DegreePosition::from_degrees(200.0));
+        dial.set_rings(vec![
+            DialRing::new("Natal", vec![sun], egui::Color32::BLUE),
+            DialRing::new("Transits", vec![moon], egui::Color32::RED),
+        ]);
+        assert_eq!(dial.rings().len(), 2);
+        assert_eq!(dial.points(), &[sun]);
+        assert!(dial.ring_radius(1) < dial.ring_radius(0));
+        
+        // Each ring is laid out at its own radius
+        let placed = dial.layout_points();
+        assert_eq!(placed.len(), 2);
+        assert_eq!(placed[1].ring, 1);
+        assert!((placed[1].exact.distance(dial.center) - dial.ring_radius(1)).abs() < 1e-3);
+        
+        // Setting points replaces only the first ring
+        dial.set_points(vec![moon]);
+        assert_eq!(dial.rings()[0].label, "Natal");
+        assert_eq!(dial.points(), &[moon]);
+    }
+    
+    #[test]
+    fn test_spread_angles() {
+        // Lone points stay where they are
+        assert_eq!(spread_angles(&[10.0, 100.0], 5.0), vec![10.0, 100.0]);
+        
+        // A pair fans out evenly around its mean
+        let pair = spread_angles(&[100.0, 101.0], 5.0);
+        assert!((pair[0] - 98.0).abs() < 1e-4 && (pair[1] - 103.0).abs() < 1e-4);
+        
+        // Across 0° Aries, with the input order kept
+        let across = spread_angles(&[1.0, 359.0], 5.0);
+        assert!((across[0] - 2.5).abs() < 1e-4 && (across[1] - 357.5).abs() < 1e-4);
+        
+        // A stellium pulls in its neighbour and keeps every gap
+        let stellium = spread_angles(&[50.0, 51.0, 52.0, 56.0, 200.0], 5.0);
+        let mut sorted = stellium.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        assert!(sorted.windows(2).all(|w| w[1] - w[0] >= 5.0 - 1e-3));
+        assert_eq!(stellium[4], 200.0);
+        
+        // More points than fit are spaced evenly round the ring
+        let crowded = spread_angles(&[0.0; 100], 5.0);
+        let mut sorted = crowded.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        assert!(sorted.windows(2).all(|w| (w[1] - w[0] - 3.6).abs() < 1e-2));
+    }
+    
+    #[test]
+    fn test_tick_steps() {
+        // The 360° face keeps its 1°, 5° and labelled 30° ticks
+        assert_eq!(tick_steps(360.0), (3600, 18_000, 108_000));
+        // 90° dial: 15' ticks, 5° marks, labels every 10°
+        assert_eq!(tick_steps(90.0), (900, 18_000, 36_000));
+        assert_eq!(tick_steps(45.0), (600, 3600, 18_000));
+        assert_eq!(tick_steps(22.5), (300, 1800, 18_000));
+        // 7th harmonic, 51°25'43"
+        assert_eq!(tick_steps(360.0 / 7.0), (600, 3600, 18_000));
+        for n in 1..=64 {
+            let (minor, medium, major) = tick_steps(360.0 / n as f64);
+            assert_eq!(medium % minor, 0, "harmonic {}", n);
+            assert_eq!(major % minor, 0, "harmonic {}", n);
+        }
+    }
+    
+    #[test]
+    fn test_harmonic_face() {
+        let mut dial = Dial::new();
+        dial.set_harmonic(Harmonic::Fourth);
+        assert_eq!(dial.face_span(), 90.0);
+        // 100° is 10° on the 90° dial, a ninth of the way round
+        assert_eq!(dial.face_angle(100.0), 40.0);
+        
+        dial.set_points(vec![(PointId::Body(Body::Sun), DegreePosition::from_degrees(100.0))]);
+        dial.set_position(DegreePosition::from_degrees(10.5));
+        assert_eq!(dial.pointed_at(), Some((0, PointId::Body(Body::Sun))));
+        
+        dial.set_harmonic_face(false);
+        assert_eq!(dial.face_span(), 360.0);
+        assert_eq!(dial.face_angle(100.0), 100.0);
+        assert_eq!(dial.pointed_at(), None);
+    }
+    
+    #[test]
+    fn test_mirrored_points() {
+        let mut dial = Dial::new();
+        dial.set_points(vec![(PointId::Body(Body::Venus), DegreePosition::from_degrees(70.0))]);
+        // With the red arm on 0° Cancer the reflections are the antiscia
+        dial.set_position(DegreePosition::from_degrees(90.0));
+        assert_eq!(dial.mirrored_points()[0].1, DegreePosition::from_degrees(110.0));
+        dial.set_position(DegreePosition::from_degrees(0.0));
+        assert_eq!(dial.mirrored_points()[0].1, DegreePosition::from_degrees(290.0));
+    }
     
     #[test]
     fn test_calculate_angle() {
@@ -0,0 +1,530 @@
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+use chrono::{Days, Utc};
+use egui::{self, Ui};
+use crate::astrology::chart::Chart;
+use crate::astrology::graphic_ephemeris::GraphicEphemeris;
+use crate::astrology::transits::SearchProgress;
+use crate::astrology::{time, Body, Harmonic};
+use crate::ui::dial::tick_steps;
+use crate::ui::transits_panel::parse_date_range;
+
+/// Narrowest view in days the plot zooms in to
+const MIN_VIEW_DAYS: f64 = 1.0;
+
+/// Size in pixels of exported images
+const EXPORT_SIZE: (f64, f64) = (1200.0, 600.0);
+
+/// Margin in pixels left of the plot for the natal labels
+const LABEL_MARGIN: f32 = 30.0;
+
+/// Colour of each body's trace, so the classical planets stand apart
+fn body_color(body: Body) -> egui::Color32 {
+    match body {
+        Body::Sun => egui::Color32::from_rgb(230, 160, 0),
+        Body::Moon => egui::Color32::from_rgb(120, 120, 160),
+        Body::Mercury => egui::Color32::from_rgb(0, 150, 150),
+        Body::Venus => egui::Color32::from_rgb(40, 170, 60),
+        Body::Mars => egui::Color32::from_rgb(210, 40, 40),
+        Body::Jupiter => egui::Color32::from_rgb(130, 60, 180),
+        Body::Saturn => egui::Color32::from_rgb(90, 70, 40),
+        Body::Uranus => egui::Color32::from_rgb(30, 110, 220),
+        Body::Neptune => egui::Color32::from_rgb(20, 60, 140),
+        Body::Pluto => egui::Color32::from_rgb(120, 20, 60),
+        _ => egui::Color32::from_gray(140),
+    }
+}
+
+/// A plot being calculated on a worker thread
+struct RunningPlot {
+    progress: Arc<SearchProgress>,
+    result: Receiver<GraphicEphemeris>,
+}
+
+/// What a plot was asked for, kept to sample it again for a narrower view
+struct PlotRequest {
+    bodies: Vec<Body>,
+    chart: Option<Chart>,
+    harmonic: Harmonic,
+    /// Julian Days of the whole period
+    period: (f64, f64),
+}
+
+/// Panel plotting the graphic ephemeris of the transiting bodies against a chart
+pub struct EphemerisPanel {
+    start: String,
+    end: String,
+    /// Plot the Moon, whose trace crowds out the others on long periods
+    moon: bool,
+    transneptunians: bool,
+    request: Option<PlotRequest>,
+    /// Traces of the period, or of the part of it around the view when zoomed in
+    graph: Option<GraphicEphemeris>,
+    /// Julian Days of the period shown, within the one requested
+    view: (f64, f64),
+    /// Set when the view moved, to check whether the traces still cover it
+    view_moved: bool,
+    path: String,
+    status: Option<String>,
+    running: Option<RunningPlot>,
+}
+
+impl Default for EphemerisPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EphemerisPanel {
+    /// A panel for the year from today
+    pub fn new() -> Self {
+        let today = Utc::now().date_naive();
+        let next_year = today.checked_add_days(Days::new(365)).unwrap_or(today);
+        Self {
+            start: today.format("%Y-%m-%d").to_string(),
+            end: next_year.format("%Y-%m-%d").to_string(),
+            moon: false,
+            transneptunians: false,
+            request: None,
+            graph: None,
+            view: (0.0, 0.0),
+            view_moved: false,
+            path: "ephemeris.svg".to_string(),
+            status: None,
+            running: None,
+        }
+    }
+
+    /// Forgets the plot made for a previous chart, stopping one still being calculated
+    pub fn clear(&mut self) {
+        self.cancel();
+        self.request = None;
+        self.graph = None;
+        self.status = None;
+    }
+
+    /// Stops the plot being calculated, if any
+    pub fn cancel(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.progress.cancel();
+        }
+    }
+
+    pub fn is_plotting(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Bodies plotted with the current settings
+    fn bodies(&self) -> Vec<Body> {
+        Body::CLASSICAL
+            .into_iter()
+            .filter(|&body| self.moon || body != Body::Moon)
+            .chain(Body::TRANSNEPTUNIANS.into_iter().filter(|_| self.transneptunians))
+            .collect()
+    }
+
+    /// Starts calculating the traces for the period on a harmonic dial on a
+    /// worker thread, replacing any plot still being calculated, and shows
+    /// the whole period
+    pub fn calculate(&mut self, chart: Option<&Chart>, harmonic: Harmonic) {
+        self.cancel();
+        let period = match parse_date_range(&self.start, &self.end) {
+            Ok(range) => range,
+            Err(err) => {
+                self.status = Some(err);
+                return;
+            }
+        };
+        self.request = Some(PlotRequest {
+            bodies: self.bodies(),
+            chart: chart.cloned(),
+            harmonic,
+            period,
+        });
+        self.graph = None;
+        self.view = period;
+        self.status = None;
+        self.sample(period);
+    }
+
+    /// Starts calculating the traces of the requested plot over some days on a
+    /// worker thread, keeping the current traces on show until it is done
+    fn sample(&mut self, (start, end): (f64, f64)) {
+        self.cancel();
+        let Some(request) = &self.request else {
+            return;
+        };
+        let progress = Arc::new(SearchProgress::default());
+        let (sender, result) = mpsc::channel();
+        let bodies = request.bodies.clone();
+        let chart = request.chart.clone();
+        let harmonic = request.harmonic;
+        let shared = Arc::clone(&progress);
+        thread::spawn(move || {
+            let graph = GraphicEphemeris::with_progress(&bodies, chart.as_ref(), harmonic, start, end, &shared);
+            // The panel may have moved on and dropped the receiver
+            let _ = sender.send(graph);
+        });
+        self.running = Some(RunningPlot { progress, result });
+    }
+
+    /// Samples again around the view once it has moved off the traces, or
+    /// zoomed in further than they follow the bodies
+    fn refresh(&mut self) {
+        if !self.view_moved || self.is_plotting() {
+            return;
+        }
+        self.view_moved = false;
+        let (Some(request), Some(graph)) = (&self.request, &self.graph) else {
+            return;
+        };
+        if graph.covers(self.view.0, self.view.1) {
+            return;
+        }
+        // Half a view either side, so that small moves need no new traces
+        let margin = (self.view.1 - self.view.0) / 2.0;
+        let range = (
+            (self.view.0 - margin).max(request.period.0),
+            (self.view.1 + margin).min(request.period.1),
+        );
+        self.sample(range);
+    }
+
+    /// Takes the plot once it is calculated. Returns whether one is still being calculated.
+    pub fn poll(&mut self) -> bool {
+        let Some(running) = &self.running else {
+            return false;
+        };
+        match running.result.try_recv() {
+            Ok(graph) => self.graph = Some(graph),
+            Err(TryRecvError::Empty) => return true,
+            Err(TryRecvError::Disconnected) => self.status = Some("The plot stopped unexpectedly".to_string()),
+        }
+        self.running = None;
+        false
+    }
+
+    /// Scales the view about a Julian Day, zooming in with factors below one
+    pub fn zoom(&mut self, factor: f64, anchor: f64) {
+        let Some(request) = &self.request else {
+            return;
+        };
+        let (first, last) = request.period;
+        let width = ((self.view.1 - self.view.0) * factor).clamp(MIN_VIEW_DAYS, last - first);
+        let ratio = (anchor - self.view.0) / (self.view.1 - self.view.0);
+        let start = anchor - ratio * width;
+        self.view = (start, start + width);
+        self.pan(0.0);
+    }
+
+    /// Moves the view by some days, keeping it within the requested period
+    pub fn pan(&mut self, days: f64) {
+        let Some(request) = &self.request else {
+            return;
+        };
+        let (first, last) = request.period;
+        let width = self.view.1 - self.view.0;
+        let start = (self.view.0 + days).clamp(first, last - width);
+        self.view = (start, start + width);
+        self.view_moved = true;
+    }
+
+    /// Renders the current view as an SVG image
+    pub fn to_svg(&self) -> Option<String> {
+        let graph = self.graph.as_ref()?;
+        let (width, height) = EXPORT_SIZE;
+        let span = graph.span();
+        let x = |jd: f64| (jd - self.view.0) / (self.view.1 - self.view.0) * width;
+        let y = |position: f64| height - position / span * height;
+        let hex = |color: egui::Color32| format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b());
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n\
+             <clipPath id=\"plot\"><rect width=\"{w}\" height=\"{h}\"/></clipPath>\n\
+             <rect width=\"{w}\" height=\"{h}\" fill=\"white\"/>\n",
+            w = width,
+            h = height
+        );
+        for &(point, position) in &graph.natal {
+            svg += &format!(
+                "<line x1=\"0\" y1=\"{y:.2}\" x2=\"{w}\" y2=\"{y:.2}\" stroke=\"#bbbbbb\" stroke-dasharray=\"4 3\"/>\n\
+                 <text x=\"2\" y=\"{ty:.2}\" font-size=\"10\" fill=\"#666666\">{label}</text>\n",
+                y = y(position),
+                ty = y(position) - 2.0,
+                w = width,
+                label = point.abbreviation()
+            );
+        }
+        svg += "<g clip-path=\"url(#plot)\" fill=\"none\">\n";
+        for trace in &graph.traces {
+            for segment in &trace.segments {
+                let points: Vec<String> = segment
+                    .iter()
+                    .map(|&(jd, position)| format!("{:.2},{:.2}", x(jd), y(position)))
+                    .collect();
+                svg += &format!(
+                    "<polyline stroke=\"{}\" stroke-width=\"1.5\" points=\"{}\"/>\n",
+                    hex(body_color(trace.body)),
+                    points.join(" ")
+                );
+            }
+        }
+        svg += "</g>\n</svg>\n";
+        Some(svg)
+    }
+
+    /// Shows the controls and the plot, for a chart on a harmonic dial
+    pub fn ui(&mut self, ui: &mut Ui, chart: Option<&Chart>, harmonic: Harmonic) {
+        self.poll();
+        self.refresh();
+        let plotting = self.is_plotting();
+        ui.horizontal(|ui| {
+            ui.label("From:");
+            ui.add(egui::TextEdit::singleline(&mut self.start).desired_width(80.0));
+            ui.label("to:");
+            ui.add(egui::TextEdit::singleline(&mut self.end).desired_width(80.0));
+            ui.checkbox(&mut self.moon, "Moon");
+            ui.checkbox(&mut self.transneptunians, "Transneptunians");
+            if plotting {
+                let fraction = self.running.as_ref().map_or(0.0, |running| running.progress.fraction());
+                ui.add(egui::ProgressBar::new(fraction).show_percentage().desired_width(120.0));
+                if ui.button("Cancel").clicked() {
+                    self.cancel();
+                }
+                ui.ctx().request_repaint();
+            } else if ui.button(format!("Plot {}° sweep", harmonic.angle_span())).clicked() {
+                self.calculate(chart, harmonic);
+            }
+        });
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+        let Some(graph) = &self.graph else {
+            return;
+        };
+        if !graph.skipped.is_empty() {
+            let names: Vec<&str> = graph.skipped.iter().map(|body| body.name()).collect();
+            ui.label(format!("Too fast to follow over this period, zoom in to see: {}", names.join(", ")));
+        }
+
+        let height = ui.available_height().clamp(200.0, 600.0) - 30.0;
+        let size = egui::vec2(ui.available_width().max(200.0), height);
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::drag());
+        let rect = response.rect;
+        let plot = egui::Rect::from_min_max(rect.min + egui::vec2(LABEL_MARGIN, 0.0), rect.max);
+        let span = graph.span();
+        let days = self.view.1 - self.view.0;
+        let x = |jd: f64| plot.left() + ((jd - self.view.0) / days) as f32 * plot.width();
+        let y = |position: f64| plot.bottom() - (position / span) as f32 * plot.height();
+        painter.rect_filled(plot, 0.0, egui::Color32::WHITE);
+
+        // Degree grid at the dial's labelled spacing
+        let (_, _, major) = tick_steps(span);
+        let grid = egui::Stroke::new(0.5, egui::Color32::from_gray(220));
+        let mut degree = 0.0;
+        while degree < span {
+            let level = y(degree);
+            painter.line_segment([egui::pos2(plot.left(), level), egui::pos2(plot.right(), level)], grid);
+            degree += major as f64 / 3600.0;
+        }
+        // Ten date lines across the view
+        for i in 0..=10 {
+            let jd = self.view.0 + days * i as f64 / 10.0;
+            painter.line_segment([egui::pos2(x(jd), plot.top()), egui::pos2(x(jd), plot.bottom())], grid);
+            if let Some(date) = time::to_datetime(jd) {
+                painter.text(
+                    egui::pos2(x(jd), plot.bottom() + 2.0),
+                    egui::Align2::CENTER_TOP,
+                    date.format("%Y-%m-%d").to_string(),
+                    egui::FontId::proportional(9.0),
+                    egui::Color32::from_gray(100),
+                );
+            }
+        }
+
+        for &(point, position) in &graph.natal {
+            let level = y(position);
+            painter.line_segment(
+                [egui::pos2(plot.left(), level), egui::pos2(plot.right(), level)],
+                egui::Stroke::new(0.5, egui::Color32::from_gray(150)),
+            );
+            painter.text(
+                egui::pos2(rect.left() + 2.0, level),
+                egui::Align2::LEFT_CENTER,
+                point.abbreviation(),
+                egui::FontId::monospace(10.0),
+                egui::Color32::from_gray(90),
+            );
+        }
+
+        let clipped = painter.with_clip_rect(plot);
+        for trace in &graph.traces {
+            let stroke = egui::Stroke::new(1.5, body_color(trace.body));
+            for segment in &trace.segments {
+                let visible: Vec<egui::Pos2> = segment
+                    .iter()
+                    .filter(|&&(jd, _)| jd >= self.view.0 - days * 0.01 && jd <= self.view.1 + days * 0.01)
+                    .map(|&(jd, position)| egui::pos2(x(jd), y(position)))
+                    .collect();
+                if visible.len() > 1 {
+                    clipped.add(egui::Shape::line(visible, stroke));
+                }
+            }
+            if let Some(&(jd, position)) = trace.segments.first().and_then(|segment| segment.first()) {
+                clipped.text(
+                    egui::pos2(x(jd) + 2.0, y(position)),
+                    egui::Align2::LEFT_BOTTOM,
+                    trace.body.abbreviation(),
+                    egui::FontId::monospace(10.0),
+                    stroke.color,
+                );
+            }
+        }
+
+        // Drag to pan, scroll to zoom about the pointer
+        let days_per_pixel = days / plot.width() as f64;
+        if response.dragged() {
+            self.pan(-response.drag_delta().x as f64 * days_per_pixel);
+        }
+        if let Some(pointer) = response.hover_pos() {
+            let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+            let anchor = self.view.0 + (pointer.x - plot.left()) as f64 * days_per_pixel;
+            if scroll != 0.0 {
+                self.zoom((-scroll as f64 * 0.002).exp(), anchor);
+            }
+            if plot.contains(pointer) {
+                let position = (plot.bottom() - pointer.y) as f64 / plot.height() as f64 * span;
+                let date = time::to_datetime(anchor)
+                    .map_or_else(String::new, |date| date.format("%Y-%m-%d").to_string());
+                painter.text(
+                    plot.right_top() + egui::vec2(-4.0, 4.0),
+                    egui::Align2::RIGHT_TOP,
+                    format!("{}  {:.2}°", date, position),
+                    egui::FontId::monospace(11.0),
+                    egui::Color32::BLACK,
+                );
+            }
+        }
+
+        ui.add_space(14.0);
+        ui.horizontal(|ui| {
+            if ui.button("Show all").clicked() {
+                if let Some(request) = &self.request {
+                    self.view = request.period;
+                    self.view_moved = true;
+                }
+            }
+            ui.label("File:");
+            ui.text_edit_singleline(&mut self.path);
+            if ui.button("Export SVG").clicked() {
+                if let Some(svg) = self.to_svg() {
+                    self.status = Some(match std::fs::write(&self.path, svg) {
+                        Ok(()) => format!("Exported to {}", self.path),
+                        Err(err) => err.to_string(),
+                    });
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Waits for the plot being calculated
+    fn finish(panel: &mut EphemerisPanel) {
+        while panel.poll() {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_zoom_and_pan() {
+        let mut panel = EphemerisPanel::new();
+        panel.start = "2024-01-01".to_string();
+        panel.end = "2024-12-31".to_string();
+        panel.calculate(None, Harmonic::Eighth);
+        assert!(panel.is_plotting());
+        finish(&mut panel);
+        let (start, end) = panel.view;
+        assert!((end - start - 365.0).abs() < 1e-9);
+
+        // Zooming in about the middle keeps the middle in place
+        let middle = (start + end) / 2.0;
+        panel.zoom(0.5, middle);
+        assert!((panel.view.1 - panel.view.0 - 182.5).abs() < 1e-9);
+        assert!(((panel.view.0 + panel.view.1) / 2.0 - middle).abs() < 1e-9);
+
+        // Panning stops at the ends of the period, zooming out at all of it
+        panel.pan(-1000.0);
+        assert_eq!(panel.view.0, start);
+        panel.zoom(10.0, start);
+        assert_eq!(panel.view, (start, end));
+    }
+
+    #[test]
+    fn test_resample_on_zoom() {
+        // Over twenty years the Moon is too fast to plot, but a month of it can be
+        let mut panel = EphemerisPanel::new();
+        panel.start = "2000-01-01".to_string();
+        panel.end = "2020-01-01".to_string();
+        panel.moon = true;
+        panel.calculate(None, Harmonic::Eighth);
+        finish(&mut panel);
+        assert_eq!(panel.graph.as_ref().unwrap().skipped, [Body::Moon]);
+        let period = panel.view;
+
+        panel.zoom(30.0 / (period.1 - period.0), period.0 + 1000.0);
+        panel.refresh();
+        assert!(panel.is_plotting());
+        finish(&mut panel);
+        let graph = panel.graph.as_ref().unwrap();
+        assert!(graph.skipped.is_empty());
+        assert!(graph.covers(panel.view.0, panel.view.1));
+        assert!(graph.end - graph.start < 61.0);
+
+        // Small moves stay on the traces, and zooming out again samples the period
+        panel.pan(5.0);
+        panel.refresh();
+        assert!(!panel.is_plotting());
+        panel.zoom(1e6, period.0);
+        assert_eq!(panel.view, period);
+        panel.refresh();
+        finish(&mut panel);
+        assert_eq!(panel.graph.as_ref().unwrap().skipped, [Body::Moon]);
+    }
+
+    #[test]
+    fn test_svg_export() {
+        let mut panel = EphemerisPanel::new();
+        assert!(panel.to_svg().is_none());
+        panel.start = "2024-01-01".to_string();
+        panel.end = "2024-03-01".to_string();
+        panel.calculate(None, Harmonic::Fourth);
+        finish(&mut panel);
+        let svg = panel.to_svg().unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.matches("<polyline").count() >= panel.bodies().len());
+
+        panel.end = "2023-01-01".to_string();
+        panel.calculate(None, Harmonic::Fourth);
+        assert!(!panel.is_plotting());
+        assert!(panel.status.is_some());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut panel = EphemerisPanel::new();
+        panel.start = "1900-01-01".to_string();
+        panel.end = "2100-01-01".to_string();
+        panel.moon = true;
+        panel.calculate(None, Harmonic::Eighth);
+        panel.cancel();
+        assert!(!panel.poll());
+        assert!(panel.to_svg().is_none());
+    }
+}
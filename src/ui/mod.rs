@@ -1,6 +1,14 @@
 pub mod dial;
 pub mod controls;
+pub mod chart_editor;
+pub mod ephemeris_panel;
+pub mod pictures_panel;
+pub mod transits_panel;
 
 // Re-export commonly used types
 pub use dial::Dial;
-pub use controls::DialControls;
\ No newline at end of file
+pub use controls::DialControls;
+pub use chart_editor::ChartEditor;
+pub use ephemeris_panel::EphemerisPanel;
+pub use pictures_panel::PicturesPanel;
+pub use transits_panel::TransitsPanel;
\ No newline at end of file
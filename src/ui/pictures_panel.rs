@@ -0,0 +1,149 @@
+use egui::{self, Ui};
+use crate::astrology::chart::{Chart, PointId};
+use crate::astrology::constants::DEFAULT_MIDPOINT_ORB;
+use crate::astrology::pictures::{self, Formula, PictureList, PlanetaryPicture};
+use crate::astrology::Harmonic;
+
+/// Panel for evaluating formulas and listing a chart's planetary pictures
+pub struct PicturesPanel {
+    formula: String,
+    orb: f64,
+    filter: String,
+    path: String,
+    pictures: Option<PictureList>,
+    status: Option<String>,
+}
+
+impl Default for PicturesPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PicturesPanel {
+    pub fn new() -> Self {
+        Self {
+            formula: "SU+MO-AS".to_string(),
+            orb: DEFAULT_MIDPOINT_ORB as f64,
+            filter: String::new(),
+            path: "pictures.csv".to_string(),
+            pictures: None,
+            status: None,
+        }
+    }
+
+    /// Forgets pictures found for a previous chart
+    pub fn clear(&mut self) {
+        self.pictures = None;
+        self.status = None;
+    }
+
+    /// Parses the filter field into points, describing the first unknown one
+    fn filter_points(&self) -> Result<Vec<PointId>, String> {
+        self.filter
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|name| !name.is_empty())
+            .map(|name| PointId::from_abbreviation(name).ok_or_else(|| format!("Unknown point \"{}\"", name)))
+            .collect()
+    }
+
+    /// Pictures of the last search that pass the filter
+    pub fn filtered(&self) -> Vec<PlanetaryPicture> {
+        match (&self.pictures, self.filter_points()) {
+            (Some(list), Ok(points)) => list.involving(&points).cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Shows the panel for the current chart, searching on the given harmonic dial
+    pub fn ui(&mut self, ui: &mut Ui, chart: Option<&Chart>, harmonic: Harmonic) {
+        let Some(chart) = chart else {
+            ui.label("No chart");
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Formula:");
+            ui.text_edit_singleline(&mut self.formula);
+        });
+        match self.formula.parse::<Formula>().and_then(|formula| formula.evaluate(chart)) {
+            Ok(position) => ui.monospace(format!("= {}", position.format())),
+            Err(err) => ui.colored_label(egui::Color32::RED, err.to_string()),
+        };
+
+        ui.separator();
+        ui.add(egui::Slider::new(&mut self.orb, 0.1..=3.0).text("Orb").suffix("°"));
+        if ui.button(format!("Search {}° dial", harmonic.angle_span())).clicked() {
+            let list = PictureList::search(chart.points(), harmonic, self.orb);
+            self.status = Some(format!("{} pictures", list.len()));
+            self.pictures = Some(list);
+        }
+
+        if self.pictures.is_none() {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label("Containing:");
+            ui.text_edit_singleline(&mut self.filter);
+        });
+        if let Err(err) = self.filter_points() {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+
+        let filtered = self.filtered();
+        egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            egui::Grid::new("picture_list").striped(true).show(ui, |ui| {
+                for picture in &filtered {
+                    ui.label(picture.to_string());
+                    ui.monospace(format!("{:.2}°", picture.orb));
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("File:");
+            ui.text_edit_singleline(&mut self.path);
+        });
+        if ui.button("Export CSV").clicked() {
+            let csv = pictures::to_csv(&filtered);
+            self.status = Some(match std::fs::write(&self.path, csv) {
+                Ok(()) => format!("Exported {} pictures to {}", filtered.len(), self.path),
+                Err(err) => err.to_string(),
+            });
+        }
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::BirthData;
+    use crate::astrology::Body;
+    use chrono::{FixedOffset, TimeZone};
+
+    #[test]
+    fn test_filter() {
+        let datetime = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 1, 1, 12, 0, 0)
+            .unwrap();
+        let chart = Chart::new("Sample", BirthData::new(datetime, 0.0, 0.0).unwrap());
+
+        let mut panel = PicturesPanel::new();
+        panel.pictures = Some(PictureList::search(chart.points(), Harmonic::Fourth, 1.0));
+        let all = panel.filtered().len();
+
+        panel.filter = "su, mc".to_string();
+        let sun = PointId::Body(Body::Sun);
+        assert!(panel.filtered().len() <= all);
+        assert!(panel.filtered().iter().all(|p| p.involves(sun) && p.involves(PointId::Midheaven)));
+
+        panel.filter = "XX".to_string();
+        assert!(panel.filter_points().is_err());
+        assert!(panel.filtered().is_empty());
+    }
+}
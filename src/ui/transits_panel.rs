@@ -0,0 +1,262 @@
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+use chrono::{Days, NaiveDate, Utc};
+use egui::{self, Ui};
+use crate::astrology::chart::Chart;
+use crate::astrology::pictures::FormulaError;
+use crate::astrology::transits::{SearchProgress, TransitHit, TransitSearch, TransitTarget};
+use crate::astrology::{time, Body, Harmonic};
+
+/// Parses a period given as two YYYY-MM-DD dates into Julian Days at 0h UT
+pub fn parse_date_range(start: &str, end: &str) -> Result<(f64, f64), String> {
+    let parse = |date: &str| {
+        NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map(|date| time::julian_day(&date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()))
+            .map_err(|_| format!("Invalid date \"{}\", expected YYYY-MM-DD", date))
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    if end <= start {
+        return Err("The end date must come after the start".to_string());
+    }
+    Ok((start, end))
+}
+
+/// A search running on a worker thread
+struct RunningSearch {
+    progress: Arc<SearchProgress>,
+    result: Receiver<Result<Vec<TransitHit>, String>>,
+}
+
+/// Panel for searching when transiting bodies reach natal targets on the dial
+pub struct TransitsPanel {
+    /// A single body to follow, or all classical bodies with `None`
+    body: Option<Body>,
+    targets: String,
+    start: String,
+    end: String,
+    hits: Vec<TransitHit>,
+    status: Option<String>,
+    running: Option<RunningSearch>,
+}
+
+impl Default for TransitsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransitsPanel {
+    /// A panel searching the year from today
+    pub fn new() -> Self {
+        let today = Utc::now().date_naive();
+        let next_year = today.checked_add_days(Days::new(365)).unwrap_or(today);
+        Self {
+            body: None,
+            targets: "MC, SU/MO".to_string(),
+            start: today.format("%Y-%m-%d").to_string(),
+            end: next_year.format("%Y-%m-%d").to_string(),
+            hits: Vec::new(),
+            status: None,
+            running: None,
+        }
+    }
+
+    /// Forgets hits found for a previous chart, stopping a search still running
+    pub fn clear(&mut self) {
+        self.cancel();
+        self.hits.clear();
+        self.status = None;
+    }
+
+    /// Stops the running search, if any, keeping the previous hits
+    pub fn cancel(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.progress.cancel();
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Parses the comma-separated targets field
+    fn parse_targets(&self) -> Result<Vec<TransitTarget>, FormulaError> {
+        self.targets
+            .split(',')
+            .map(str::trim)
+            .filter(|target| !target.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    /// Starts the search for the natal chart on the given harmonic dial on a
+    /// worker thread, replacing any search still running
+    pub fn search(&mut self, chart: &Chart, harmonic: Harmonic) {
+        self.cancel();
+        let bodies = match self.body {
+            Some(body) => vec![body],
+            None => Body::CLASSICAL.to_vec(),
+        };
+        let parsed = parse_date_range(&self.start, &self.end)
+            .and_then(|range| Ok((range, self.parse_targets().map_err(|err| err.to_string())?)));
+        let ((start, end), targets) = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                self.status = Some(err);
+                self.hits.clear();
+                return;
+            }
+        };
+
+        let progress = Arc::new(SearchProgress::default());
+        let (sender, result) = mpsc::channel();
+        let search = TransitSearch::new(bodies, harmonic);
+        let chart = chart.clone();
+        let shared = Arc::clone(&progress);
+        thread::spawn(move || {
+            let hits = search
+                .find_with_progress(&chart, &targets, start, end, &shared)
+                .map_err(|err| err.to_string());
+            // The panel may have moved on and dropped the receiver
+            let _ = sender.send(hits);
+        });
+        self.running = Some(RunningSearch { progress, result });
+        self.status = None;
+    }
+
+    /// Takes the result of a finished search. Returns whether one is still running.
+    pub fn poll(&mut self) -> bool {
+        let Some(running) = &self.running else {
+            return false;
+        };
+        let result = match running.result.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return true,
+            Err(TryRecvError::Disconnected) => Err("The search stopped unexpectedly".to_string()),
+        };
+        self.running = None;
+        match result {
+            Ok(hits) => {
+                self.status = Some(format!("{} hits", hits.len()));
+                self.hits = hits;
+            }
+            Err(err) => {
+                self.status = Some(err);
+                self.hits.clear();
+            }
+        }
+        false
+    }
+
+    pub fn hits(&self) -> &[TransitHit] {
+        &self.hits
+    }
+
+    /// Shows the panel for the natal chart, searching on the given harmonic dial
+    pub fn ui(&mut self, ui: &mut Ui, chart: Option<&Chart>, harmonic: Harmonic) {
+        let Some(chart) = chart else {
+            ui.label("No chart");
+            return;
+        };
+
+        egui::ComboBox::from_label("Transiting")
+            .selected_text(self.body.map_or("Sun to Pluto", |body| body.name()))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.body, None, "Sun to Pluto");
+                for body in Body::ALL {
+                    ui.selectable_value(&mut self.body, Some(body), body.name());
+                }
+            });
+        ui.horizontal(|ui| {
+            ui.label("Targets:");
+            ui.text_edit_singleline(&mut self.targets);
+        });
+        if let Err(err) = self.parse_targets() {
+            ui.colored_label(egui::Color32::RED, err.to_string());
+        }
+        ui.horizontal(|ui| {
+            ui.label("From:");
+            ui.add(egui::TextEdit::singleline(&mut self.start).desired_width(80.0));
+            ui.label("to:");
+            ui.add(egui::TextEdit::singleline(&mut self.end).desired_width(80.0));
+        });
+
+        if self.poll() {
+            let fraction = self.running.as_ref().map_or(0.0, |running| running.progress.fraction());
+            ui.horizontal(|ui| {
+                ui.add(egui::ProgressBar::new(fraction).show_percentage().desired_width(160.0));
+                if ui.button("Cancel").clicked() {
+                    self.cancel();
+                }
+            });
+            ui.ctx().request_repaint();
+        } else if ui.button(format!("Search {}° dial", harmonic.angle_span())).clicked() {
+            self.search(chart, harmonic);
+        }
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+
+        egui::ScrollArea::vertical().id_source("transit_hits").max_height(200.0).show(ui, |ui| {
+            egui::Grid::new("transit_hits").striped(true).show(ui, |ui| {
+                for hit in &self.hits {
+                    let date = hit
+                        .datetime()
+                        .map_or_else(String::new, |date| date.format("%Y-%m-%d %H:%M").to_string());
+                    ui.monospace(date);
+                    ui.label(format!("{} = {}", hit.body.abbreviation(), hit.target));
+                    ui.label(if hit.retrograde { "R" } else { "" });
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::chart::BirthData;
+    use chrono::{FixedOffset, TimeZone};
+
+    #[test]
+    fn test_search() {
+        let datetime = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 1, 1, 12, 0, 0)
+            .unwrap();
+        let chart = Chart::new("Sample", BirthData::new(datetime, 0.0, 0.0).unwrap());
+
+        let mut panel = TransitsPanel::new();
+        panel.body = Some(Body::Sun);
+        panel.targets = "SU".to_string();
+        panel.start = "2010-06-01".to_string();
+        panel.end = "2011-06-01".to_string();
+        panel.search(&chart, Harmonic::First);
+        assert!(panel.is_searching());
+        while panel.poll() {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(panel.hits().len(), 1);
+        assert_eq!(panel.status.as_deref(), Some("1 hits"));
+
+        // A long search can be cancelled, keeping the hits found before
+        panel.body = None;
+        panel.end = "2110-06-01".to_string();
+        panel.search(&chart, Harmonic::First);
+        panel.cancel();
+        assert!(!panel.poll());
+        assert_eq!(panel.hits().len(), 1);
+
+        panel.end = "2009-01-01".to_string();
+        panel.search(&chart, Harmonic::First);
+        assert!(!panel.is_searching());
+        assert!(panel.hits().is_empty());
+        assert!(panel.status.as_deref().unwrap().contains("end date"));
+
+        panel.targets = "SU, XX".to_string();
+        assert!(panel.parse_targets().is_err());
+    }
+}
@@ -1,27 +1,27 @@
-use std::f32::consts::PI;
+use std::f64::consts::PI;
 
 /// Normalizes an angle in degrees to [0, 360)
-pub fn normalize_degrees(degrees: f32) -> f32 {
+pub fn normalize_degrees(degrees: f64) -> f64 {
     degrees.rem_euclid(360.0)
 }
 
 /// Normalizes an angle in radians to [0, 2π)
-pub fn normalize_radians(radians: f32) -> f32 {
+pub fn normalize_radians(radians: f64) -> f64 {
     radians.rem_euclid(2.0 * PI)
 }
 
 /// Converts degrees to radians
-pub fn degrees_to_radians(degrees: f32) -> f32 {
+pub fn degrees_to_radians(degrees: f64) -> f64 {
     degrees * PI / 180.0
 }
 
 /// Converts radians to degrees
-pub fn radians_to_degrees(radians: f32) -> f32 {
+pub fn radians_to_degrees(radians: f64) -> f64 {
     radians * 180.0 / PI
 }
 
 /// Calculates the smallest angular difference between two angles in degrees
-pub fn angular_difference(a: f32, b: f32) -> f32 {
+pub fn angular_difference(a: f64, b: f64) -> f64 {
     let diff = normalize_degrees(b - a);
     if diff > 180.0 {
         diff - 360.0